@@ -0,0 +1,110 @@
+use checkers_rs::{Game, Move, PdnGame};
+use wasm_bindgen::prelude::*;
+
+use crate::types::{rules_or_default, TSBoard, TSGameOutcome, TSMove, TSPlayer, TSRules};
+use crate::TSMoveArray;
+
+/// Game in progress, keeping the side to move and the move history
+#[wasm_bindgen(js_name = "Game")]
+pub struct JSGame(Game);
+
+#[wasm_bindgen(js_class = "Game")]
+impl JSGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(rules: Option<TSRules>) -> JSGame {
+        JSGame(Game::new(rules_or_default(rules)))
+    }
+
+    /// Game that starts from the board, with `toMove` making the first move
+    #[wasm_bindgen(js_name = "fromPosition")]
+    pub fn from_position(board: TSBoard, to_move: TSPlayer, rules: Option<TSRules>) -> JSGame {
+        JSGame(Game::from_position(
+            rules_or_default(rules),
+            board.into(),
+            to_move.into(),
+        ))
+    }
+
+    /// Game replayed from its PDN record, or throws telling where the record
+    /// is broken
+    #[wasm_bindgen(js_name = "fromPdn")]
+    pub fn from_pdn(pdn: &str, rules: Option<TSRules>) -> Result<JSGame, JsValue> {
+        let error = |err: checkers_rs::PdnError| js_sys::Error::new(&err.to_string()).into();
+        let record: PdnGame = pdn.parse().map_err(error)?;
+        record
+            .replay(rules_or_default(rules))
+            .map(JSGame)
+            .map_err(error)
+    }
+
+    /// PDN record of the moves played so far
+    #[wasm_bindgen(js_name = "toPdn")]
+    pub fn to_pdn(&self) -> String {
+        PdnGame::from_game(&self.0).to_string()
+    }
+
+    pub fn board(&self) -> TSBoard {
+        (*self.0.board()).into()
+    }
+
+    #[wasm_bindgen(js_name = "toMove")]
+    pub fn to_move(&self) -> TSPlayer {
+        self.0.to_move().into()
+    }
+
+    #[wasm_bindgen(js_name = "availableMoves")]
+    pub fn available_moves(&self) -> TSMoveArray {
+        self.0.available_moves().into_iter().collect()
+    }
+
+    /// Throws when the move is not available to the side to move
+    pub fn play(&mut self, mv: TSMove) -> Result<(), JsValue> {
+        let mv: Move = mv.into();
+        self.0
+            .play(&mv)
+            .map_err(|err| js_sys::Error::new(&err.to_string()).into())
+    }
+
+    #[wasm_bindgen(js_name = "canUndo")]
+    pub fn can_undo(&self) -> bool {
+        self.0.can_undo()
+    }
+
+    #[wasm_bindgen(js_name = "canRedo")]
+    pub fn can_redo(&self) -> bool {
+        self.0.can_redo()
+    }
+
+    /// Takes back the last move and returns it
+    pub fn undo(&mut self) -> Option<TSMove> {
+        let mv = self.0.undo()?;
+        Some(Move::new(self.0.board(), &mv).into())
+    }
+
+    /// Plays the last undone move again and returns it
+    pub fn redo(&mut self) -> Option<TSMove> {
+        let board = *self.0.board();
+        let mv = self.0.redo()?;
+        Some(Move::new(&board, &mv).into())
+    }
+
+    pub fn outcome(&self) -> TSGameOutcome {
+        self.0.outcome().into()
+    }
+
+    /// Ends the game in a draw, until a move gets undone
+    #[wasm_bindgen(js_name = "agreeDraw")]
+    pub fn agree_draw(&mut self) {
+        self.0.agree_draw()
+    }
+
+    /// Goes back to the start, keeping every move available to `redo`
+    pub fn rewind(&mut self) {
+        self.0.rewind()
+    }
+
+    /// Moves played from the start of the game
+    pub fn history(&self) -> TSMoveArray {
+        self.0.played_moves().into_iter().collect()
+    }
+}
@@ -1,12 +1,22 @@
+mod game;
+mod move_builder;
 mod types;
-use checkers_rs::{Board, Move, Position, Sizes};
-use types::{Solution, TSBoard, TSMove, TSPlayer, TSPosition, TSSizes, TSSolution};
+use checkers_rs::{
+    Board, Clock, Move, Position, Rules, SearchLimits, Setup, Sizes, Stop, TranspositionTable,
+    Variant,
+};
+use types::{
+    rules_or_default, Solution, TSBoard, TSMove, TSPlayer, TSPosition, TSRules, TSSearchLimits,
+    TSSearchResult, TSSetup, TSSizes, TSSolution,
+};
 
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+use std::cell::RefCell;
 use std::iter::FromIterator;
+use std::time::Duration;
 
 use wasm_bindgen::prelude::*;
 
@@ -63,35 +73,208 @@ pub fn sizes() -> TSSizes {
 }
 
 #[wasm_bindgen(js_name = "initializeBoard")]
-pub fn initialize_board() -> TSBoard {
-    Board::default().into()
+pub fn initialize_board(rules: Option<TSRules>) -> TSBoard {
+    rules_or_default(rules).initial_board().into()
 }
 
 #[wasm_bindgen(js_name = "movesFor")]
-pub fn moves_for(board: TSBoard, position: TSPosition) -> TSMoveArray {
-    checkers_rs::moves_for(&board.into(), position.into())
+pub fn moves_for(board: TSBoard, position: TSPosition, rules: Option<TSRules>) -> TSMoveArray {
+    checkers_rs::moves_for(&board.into(), position.into(), &rules_or_default(rules))
         .map(TSMoveArray::from_iter)
         .unwrap_or_default()
 }
 
 #[wasm_bindgen(js_name = "canEat")]
-pub fn can_eat(board: TSBoard, player: TSPlayer) -> TSPositionArray {
-    checkers_rs::can_eat(&board.into(), player.into()).collect()
+pub fn can_eat(board: TSBoard, player: TSPlayer, rules: Option<TSRules>) -> TSPositionArray {
+    checkers_rs::can_eat(&board.into(), player.into(), &rules_or_default(rules)).collect()
 }
 
 #[wasm_bindgen(js_name = "availableMoves")]
-pub fn available_moves(board: TSBoard, player: TSPlayer) -> TSMoveArray {
-    checkers_rs::available_moves(&board.into(), player.into()).collect()
+pub fn available_moves(board: TSBoard, player: TSPlayer, rules: Option<TSRules>) -> TSMoveArray {
+    let rules = rules_or_default(rules);
+    checkers_rs::available_moves(&board.into(), player.into(), &rules).collect()
 }
 
 #[wasm_bindgen]
-pub fn minimax(board: TSBoard, player: TSPlayer, depth: u8) -> Option<TSSolution> {
-    Solution::from_checkers(checkers_rs::minimax(&board.into(), player.into(), depth))
+pub fn minimax(
+    board: TSBoard,
+    player: TSPlayer,
+    depth: u8,
+    rules: Option<TSRules>,
+) -> Option<TSSolution> {
+    let rules = rules_or_default(rules);
+    Solution::from_checkers(checkers_rs::minimax(&board.into(), player.into(), depth, &rules))
         .map(TSSolution::from)
 }
 
+thread_local! {
+    /// Table kept between searches of the worker, with the rules its entries
+    /// were found under
+    static TABLE: RefCell<(TranspositionTable, Rules)> = RefCell::default();
+}
+
+/// Replaces the transposition table used by `alphabeta` with an empty one of
+/// the given size
+#[wasm_bindgen(js_name = "setTranspositionTableSize")]
+pub fn set_transposition_table_size(megabytes: usize) {
+    TABLE.with(|table| table.borrow_mut().0 = TranspositionTable::new(megabytes));
+}
+
+/// Runs `f` with the shared table, emptying it first when its entries were
+/// found under other rules
+fn with_table<T>(rules: &Rules, f: impl FnOnce(&mut TranspositionTable) -> T) -> T {
+    TABLE.with(|cell| {
+        let (table, table_rules) = &mut *cell.borrow_mut();
+        if table_rules != rules {
+            table.clear();
+            *table_rules = *rules;
+        }
+        f(table)
+    })
+}
+
+/// Searches `depth` plies deep. Once `shouldStop` returns true, which it is
+/// asked every few thousand nodes, gives the best move of the deepest search
+/// that completed instead.
 #[wasm_bindgen]
-pub fn alphabeta(board: TSBoard, player: TSPlayer, depth: u8) -> Option<TSSolution> {
-    Solution::from_checkers(checkers_rs::alphabeta(&board.into(), player.into(), depth))
-        .map(TSSolution::from)
+pub fn alphabeta(
+    board: TSBoard,
+    player: TSPlayer,
+    depth: u8,
+    rules: Option<TSRules>,
+    should_stop: Option<js_sys::Function>,
+) -> Option<TSSolution> {
+    let rules = rules_or_default(rules);
+    let board = board.into();
+    let player = player.into();
+    let solution = with_table(&rules, |table| match should_stop {
+        None => checkers_rs::alphabeta_with_table(&board, player, depth, &rules, table),
+        Some(should_stop) => {
+            let limits = SearchLimits {
+                max_depth: depth,
+                ..SearchLimits::default()
+            };
+            let stop = JSStop(Some(should_stop));
+            checkers_rs::iterative_deepening(
+                &board,
+                player,
+                &rules,
+                &limits,
+                &JSClock(None),
+                &stop,
+                table,
+            )
+            .solution()
+        }
+    });
+    Solution::from_checkers(solution).map(TSSolution::from)
+}
+
+/// Clock reading milliseconds from a JS function like `performance.now`, or
+/// from `Date.now` when there is none
+struct JSClock(Option<js_sys::Function>);
+
+impl Clock for JSClock {
+    fn now(&self) -> Duration {
+        let millis = self
+            .0
+            .as_ref()
+            .and_then(|now| now.call0(&JsValue::NULL).ok())
+            .and_then(|millis| millis.as_f64())
+            .unwrap_or_else(js_sys::Date::now);
+        Duration::from_secs_f64(millis.max(0.0) / 1000.0)
+    }
+}
+
+/// Stop signal polled from a JS function, like one reading a flag of a
+/// `SharedArrayBuffer` that the page sets
+struct JSStop(Option<js_sys::Function>);
+
+impl Stop for JSStop {
+    fn should_stop(&self) -> bool {
+        let should_stop = self.0.as_ref().and_then(|f| f.call0(&JsValue::NULL).ok());
+        should_stop.is_some_and(|stop| stop.is_truthy())
+    }
+}
+
+/// Searches deeper and deeper until one of the limits is reached or
+/// `shouldStop` returns true, and returns the best move of the last search
+/// that completed. `clock` gives the current time in milliseconds, `Date.now`
+/// by default.
+#[wasm_bindgen]
+pub fn search(
+    board: TSBoard,
+    player: TSPlayer,
+    limits: Option<TSSearchLimits>,
+    rules: Option<TSRules>,
+    clock: Option<js_sys::Function>,
+    should_stop: Option<js_sys::Function>,
+) -> TSSearchResult {
+    let rules = rules_or_default(rules);
+    let limits = limits.map(types::SearchLimits::from).unwrap_or_default();
+    let result = with_table(&rules, |table| {
+        checkers_rs::iterative_deepening(
+            &board.into(),
+            player.into(),
+            &rules,
+            &limits.into(),
+            &JSClock(clock),
+            &JSStop(should_stop),
+            table,
+        )
+    });
+    types::SearchResult::from(result).into()
+}
+
+/// Board after the move, or throws an error telling why the move is not legal
+#[wasm_bindgen(js_name = "tryApply")]
+pub fn try_apply(
+    board: TSBoard,
+    player: TSPlayer,
+    from: TSPosition,
+    path: TSPositionArray,
+    rules: Option<TSRules>,
+) -> Result<TSBoard, JsValue> {
+    let board: Board = board.into();
+    let path: Vec<Position> = serde_wasm_bindgen::from_value(path.into())?;
+    board
+        .try_apply(player.into(), from.into(), &path, &rules_or_default(rules))
+        .map(TSBoard::from)
+        .map_err(|err| js_sys::Error::new(&err.to_string()).into())
+}
+
+/// Move in standard notation, like "11-15" or "22x15x8"
+#[wasm_bindgen(js_name = "formatMove")]
+pub fn format_move(board: TSBoard, mv: TSMove, rules: Option<TSRules>) -> String {
+    let mv: Move = mv.into();
+    checkers_rs::format_move(&board.into(), &mv, &rules_or_default(rules))
+}
+
+/// Legal move of the player written in standard notation, or throws telling
+/// why there is none
+#[wasm_bindgen(js_name = "parseMove")]
+pub fn parse_move(
+    board: TSBoard,
+    player: TSPlayer,
+    text: &str,
+    rules: Option<TSRules>,
+) -> Result<TSMove, JsValue> {
+    checkers_rs::parse_move(&board.into(), player.into(), text, &rules_or_default(rules))
+        .map(TSMove::from)
+        .map_err(|err| js_sys::Error::new(&err.to_string()).into())
+}
+
+/// Board and side to move of a FEN string like "W:W21,22,K30:B1,2,3", or throws
+/// telling what is wrong with it
+#[wasm_bindgen(js_name = "parseFen")]
+pub fn parse_fen(fen: &str) -> Result<TSSetup, JsValue> {
+    fen.parse::<Setup>()
+        .map(TSSetup::from)
+        .map_err(|err| js_sys::Error::new(&err.to_string()).into())
+}
+
+/// FEN string of the board and side to move
+#[wasm_bindgen(js_name = "toFen")]
+pub fn to_fen(board: TSBoard, to_move: TSPlayer) -> String {
+    Setup::new(board.into(), to_move.into()).to_string()
 }
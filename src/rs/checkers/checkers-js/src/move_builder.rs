@@ -0,0 +1,63 @@
+use wasm_bindgen::prelude::*;
+
+use crate::types::{rules_or_default, TSBoard, TSMove, TSPlayer, TSPosition, TSRules};
+use crate::TSPositionArray;
+
+/// Builds a move of a single piece hop by hop, the same way a player clicks it
+#[wasm_bindgen(js_name = "MoveBuilder")]
+pub struct JSMoveBuilder(checkers_rs::MoveBuilder);
+
+#[wasm_bindgen(js_class = "MoveBuilder")]
+impl JSMoveBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        board: TSBoard,
+        player: TSPlayer,
+        from: TSPosition,
+        rules: Option<TSRules>,
+    ) -> Result<JSMoveBuilder, JsValue> {
+        let rules = rules_or_default(rules);
+        checkers_rs::MoveBuilder::new(&board.into(), player.into(), from.into(), &rules)
+            .map(JSMoveBuilder)
+            .ok_or_else(|| js_sys::Error::new("There is no piece of the player to move").into())
+    }
+
+    #[wasm_bindgen(js_name = "canMove")]
+    pub fn can_move(&self) -> bool {
+        self.0.can_move()
+    }
+
+    #[wasm_bindgen(js_name = "nextHops")]
+    pub fn next_hops(&self) -> TSPositionArray {
+        self.0.next_hops().into_iter().collect()
+    }
+
+    pub fn path(&self) -> TSPositionArray {
+        self.0.path().iter().copied().collect()
+    }
+
+    /// Throws when the hop does not continue any legal move
+    pub fn push(&mut self, to: TSPosition) -> Result<(), JsValue> {
+        self.0
+            .push(to.into())
+            .map_err(|err| js_sys::Error::new(&err.to_string()).into())
+    }
+
+    pub fn pop(&mut self) -> Option<TSPosition> {
+        self.0.pop().map(TSPosition::from)
+    }
+
+    #[wasm_bindgen(js_name = "isComplete")]
+    pub fn is_complete(&self) -> bool {
+        self.0.is_complete()
+    }
+
+    pub fn finish(&self) -> Option<TSMove> {
+        self.0.finish().map(TSMove::from)
+    }
+
+    #[wasm_bindgen(js_name = "currentBoard")]
+    pub fn current_board(&self) -> TSBoard {
+        self.0.current_board().into()
+    }
+}
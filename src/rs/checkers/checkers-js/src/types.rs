@@ -1,5 +1,6 @@
-use checkers_rs::{Board, Cell, Move, Player, Position, Row, Sizes};
+use checkers_rs::{Board, Cell, GameOutcome, Move, Player, Position, Row, Rules, Setup, Sizes};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
 const TS_TYPES_STR: &'static str = r#"
@@ -12,9 +13,49 @@ interface Sizes{
 }
 type GameBoard = [Row, Row, Row, Row, Row, Row, Row, Row]
 type Position = [x: number, y: number]
-type RSMove = { from: Position, to: Position, nextBoard: GameBoard }
+type RSMove = {
+  from: Position
+  to: Position
+  path: Position[]
+  captured: Position[]
+  nextBoard: GameBoard
+}
 type Player = "white" | "black"
+type Rules = {
+  flyingKings?: boolean
+  menCaptureBackwards?: boolean
+  capturePriority?: "any" | "mostPieces" | "italian"
+  midCapturePromotion?: "endMove" | "continueAsQueen" | "passThrough"
+  repetitionsToDraw?: number
+  queenMovesToDraw?: number
+}
+type GameOutcome =
+  | { type: "ongoing" }
+  | { type: "win", value: [winner: Player, reason: "noPieces" | "noMoves"] }
+  | { type: "draw", value: "repetition" | "queenMoves" | "agreement" }
 type Solution = [move: RSMove, score: number]
+type Setup = { board: GameBoard, toMove: Player }
+type SearchLimits = {
+  maxDepth?: number
+  timeMs?: number
+  nodes?: number
+  quiescencePly?: number
+  quiescenceNodes?: number
+  moveOrdering?: boolean
+  pvs?: boolean
+  aspirationWindow?: number
+  lateMoveReductions?: boolean
+}
+type SearchResult = {
+  pv: RSMove[]
+  score: number
+  depth: number
+  seldepth: number
+  nodes: number
+  qnodes: number
+  elapsedMs: number
+  winner?: Player
+}
 "#;
 
 macro_rules! ts_type {
@@ -54,7 +95,18 @@ ts_type!(Move, TSMove, "RSMove");
 
 ts_type!(Player, TSPlayer, "Player");
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+ts_type!(Rules, TSRules, "Rules");
+
+ts_type!(GameOutcome, TSGameOutcome, "GameOutcome");
+
+ts_type!(Setup, TSSetup, "Setup");
+
+/// Rules passed from JS, where leaving them out means the default rules
+pub fn rules_or_default(rules: Option<TSRules>) -> Rules {
+    rules.map(Rules::from).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Solution(pub Move, pub i32);
 
 ts_type!(Solution, TSSolution, "Solution");
@@ -67,3 +119,85 @@ impl Solution {
         }
     }
 }
+
+/// Limits of `checkers_rs::SearchLimits` in units JS has numbers for
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchLimits {
+    #[serde(rename = "maxDepth", default)]
+    max_depth: Option<u8>,
+    #[serde(rename = "timeMs", default)]
+    time_ms: Option<f64>,
+    #[serde(default)]
+    nodes: Option<f64>,
+    #[serde(rename = "quiescencePly", default)]
+    quiescence_ply: Option<u8>,
+    #[serde(rename = "quiescenceNodes", default)]
+    quiescence_nodes: Option<f64>,
+    #[serde(rename = "moveOrdering", default)]
+    move_ordering: Option<bool>,
+    #[serde(default)]
+    pvs: Option<bool>,
+    /// Turned off by 0
+    #[serde(rename = "aspirationWindow", default)]
+    aspiration_window: Option<i32>,
+    #[serde(rename = "lateMoveReductions", default)]
+    late_move_reductions: Option<bool>,
+}
+
+ts_type!(SearchLimits, TSSearchLimits, "SearchLimits");
+
+impl From<SearchLimits> for checkers_rs::SearchLimits {
+    fn from(limits: SearchLimits) -> Self {
+        let defaults = checkers_rs::SearchLimits::default();
+        checkers_rs::SearchLimits {
+            max_depth: limits.max_depth.unwrap_or(defaults.max_depth),
+            time: limits
+                .time_ms
+                .map(|ms| Duration::from_secs_f64(ms.max(0.0) / 1000.0)),
+            nodes: limits.nodes.map(|nodes| nodes as u64),
+            quiescence: checkers_rs::QuiescenceLimits {
+                max_ply: limits.quiescence_ply.unwrap_or(defaults.quiescence.max_ply),
+                nodes: limits.quiescence_nodes.map(|nodes| nodes as u64),
+            },
+            move_ordering: limits.move_ordering.unwrap_or(defaults.move_ordering),
+            pvs: limits.pvs.unwrap_or(defaults.pvs),
+            aspiration_window: match limits.aspiration_window {
+                Some(width) => (width > 0).then_some(width),
+                None => defaults.aspiration_window,
+            },
+            late_move_reductions: limits
+                .late_move_reductions
+                .unwrap_or(defaults.late_move_reductions),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pv: Vec<Move>,
+    score: i32,
+    depth: u8,
+    seldepth: u8,
+    nodes: f64,
+    qnodes: f64,
+    #[serde(rename = "elapsedMs")]
+    elapsed_ms: f64,
+    winner: Option<Player>,
+}
+
+ts_type!(SearchResult, TSSearchResult, "SearchResult");
+
+impl From<checkers_rs::SearchResult> for SearchResult {
+    fn from(result: checkers_rs::SearchResult) -> Self {
+        SearchResult {
+            pv: result.pv,
+            score: result.score,
+            depth: result.depth,
+            seldepth: result.seldepth,
+            nodes: result.nodes as f64,
+            qnodes: result.qnodes as f64,
+            elapsed_ms: result.elapsed.as_secs_f64() * 1000.0,
+            winner: result.winner,
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use std::time::Instant;
 
-use checkers_rs::{alphabeta, c, minimax, Board, Player, Position, Sizes};
+use checkers_rs::{
+    alphabeta, c, iterative_deepening, minimax, Board, Player, Position, Rules, SearchLimits,
+    Sizes, TranspositionTable, ASPIRATION_WINDOW,
+};
 
 fn main() {
     let mut board = Board::default();
     println!("Sizes: {:?}", Sizes::new());
     // let position = unsafe { Position::new_unchecked(2, 5) };
 
-    // match checkers_rs::moves_for(&board, position) {
+    // match checkers_rs::moves_for(&board, position, &Rules::default()) {
     //     None => {
     //         println!("Cell {:?} is not a piece", board.cell_at(position));
     //     }
@@ -25,8 +28,13 @@ fn main() {
     board.move_cell(Position::new(c!(0), c!(5)), Position::new(c!(1), c!(4)));
     println!("{}", board);
 
-    println!("{:?}", checkers_rs::alphabeta(&board, Player::White, 3));
-    // benchmark();
+    println!(
+        "{:?}",
+        checkers_rs::alphabeta(&board, Player::White, 3, &Rules::default())
+    );
+    if std::env::args().any(|arg| arg == "--benchmark") {
+        benchmark();
+    }
 }
 
 fn benchmark() {
@@ -34,7 +42,7 @@ fn benchmark() {
     for search_depth in 2..=7 {
         for iteration in 0..5 {
             let start_time = Instant::now();
-            let _ = minimax(&board, Player::White, search_depth);
+            let _ = minimax(&board, Player::White, search_depth, &Rules::default());
             println!(
                 "rs-native\tminimax\t\t{}\t{}\t{}",
                 iteration,
@@ -47,7 +55,7 @@ fn benchmark() {
     for search_depth in 2..=12 {
         for iteration in 0..5 {
             let start_time = Instant::now();
-            let _ = alphabeta(&board, Player::White, search_depth);
+            let _ = alphabeta(&board, Player::White, search_depth, &Rules::default());
             println!(
                 "rs-native\talphabeta\t{}\t{}\t{}",
                 iteration,
@@ -56,4 +64,66 @@ fn benchmark() {
             )
         }
     }
+
+    // Every switch of the search on its own, on top of move ordering
+    let ordered = SearchLimits::default();
+    let plain = SearchLimits {
+        move_ordering: false,
+        ..ordered
+    };
+    let switches = [
+        ("unordered", plain),
+        ("ordered", ordered),
+        (
+            "pvs",
+            SearchLimits {
+                pvs: true,
+                ..ordered
+            },
+        ),
+        (
+            "aspiration",
+            SearchLimits {
+                aspiration_window: Some(ASPIRATION_WINDOW),
+                ..ordered
+            },
+        ),
+        (
+            "lmr",
+            SearchLimits {
+                late_move_reductions: true,
+                ..ordered
+            },
+        ),
+        (
+            "all",
+            SearchLimits {
+                pvs: true,
+                aspiration_window: Some(ASPIRATION_WINDOW),
+                late_move_reductions: true,
+                ..ordered
+            },
+        ),
+    ];
+    for search_depth in 2..=12 {
+        for (name, limits) in &switches {
+            let limits = SearchLimits {
+                max_depth: search_depth,
+                ..*limits
+            };
+            let result = iterative_deepening(
+                &board,
+                Player::White,
+                &Rules::default(),
+                &limits,
+                &Instant::now(),
+                &|| false,
+                &mut TranspositionTable::default(),
+            );
+            println!(
+                "rs-native\tnodes\t{}\t{}\t{}\t{}",
+                name, search_depth, result.nodes, result.score
+            )
+        }
+    }
 }
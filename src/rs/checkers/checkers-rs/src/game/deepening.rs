@@ -0,0 +1,324 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use super::evaluate::WIN_SCORE;
+use super::ordering::MoveOrder;
+use super::solution::{for_white, Outcome, QuiescenceLimits, Search, Solution, INFINITY};
+use super::transposition::TranspositionTable;
+use crate::{Board, Move, MoveDelta, Player, Variant, ZobristKey};
+
+/// Deepest iteration a search goes to when nothing else stops it first
+pub const MAX_SEARCH_DEPTH: u8 = 64;
+
+/// How many nodes are searched between two looks at the clock and the stop
+/// signal
+const POLL_INTERVAL: u64 = 1024;
+
+/// Half width of the aspiration window that suits the evaluation: the value
+/// of a man
+pub const ASPIRATION_WINDOW: i32 = 1;
+
+/// Source of time for the search. Native builds can use `Instant`, while wasm
+/// has to ask JS for the time.
+pub trait Clock {
+    /// Time passed since some fixed moment of the clock's own choosing
+    fn now(&self) -> Duration;
+}
+
+/// Time since the instant
+impl Clock for Instant {
+    fn now(&self) -> Duration {
+        self.elapsed()
+    }
+}
+
+/// Asks a running search to stop early. It gets polled every 1024 nodes, and
+/// once it says so, the search returns what it found so far.
+pub trait Stop {
+    fn should_stop(&self) -> bool;
+}
+
+/// Flag set from another thread
+impl Stop for AtomicBool {
+    fn should_stop(&self) -> bool {
+        self.load(Ordering::Relaxed)
+    }
+}
+
+impl<F: Fn() -> bool> Stop for F {
+    fn should_stop(&self) -> bool {
+        self()
+    }
+}
+
+/// When an iterative search stops going deeper. Whatever is hit first ends it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Deepest iteration. The first one is always searched, so 0 counts as 1.
+    pub max_depth: u8,
+    /// The search may overrun it by the time it takes to search about a
+    /// thousand nodes
+    pub time: Option<Duration>,
+    pub nodes: Option<u64>,
+    pub quiescence: QuiescenceLimits,
+    /// Whether moves are searched best first, as guessed from the table,
+    /// eats, and the moves that cut off elsewhere. It does not change the
+    /// score, only how many nodes it takes to find it.
+    pub move_ordering: bool,
+    /// Whether moves after the first are searched with a null window first,
+    /// which is cheaper as long as the first move stays the best. Off by
+    /// default.
+    pub pvs: bool,
+    /// Half width of the window around the score of the last iteration that
+    /// every next one starts with. The window is opened up on the side the
+    /// score falls out of, and the iteration searched again. `None`, the
+    /// default, searches every iteration with a full window.
+    pub aspiration_window: Option<i32>,
+    /// Whether quiet moves late in the order are searched a ply shallower
+    /// first. Unlike the rest, it may miss a move that only shows its worth at
+    /// the full depth, so it is off unless asked for.
+    pub late_move_reductions: bool,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        SearchLimits {
+            max_depth: MAX_SEARCH_DEPTH,
+            time: None,
+            nodes: None,
+            quiescence: QuiescenceLimits::default(),
+            move_ordering: true,
+            pvs: false,
+            aspiration_window: None,
+            late_move_reductions: false,
+        }
+    }
+}
+
+/// What the last iteration that completed found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// Principal variation: the moves both sides are expected to play,
+    /// starting with the best move. Empty when there are no moves to make.
+    pub pv: Vec<Move>,
+    /// Score of the position, positive when white is better
+    pub score: i32,
+    pub depth: u8,
+    /// Deepest ply any line of the search reached
+    pub seldepth: u8,
+    /// Nodes searched by every iteration, including the one that got stopped
+    pub nodes: u64,
+    /// Part of `nodes` that followed eats past the depth
+    pub qnodes: u64,
+    pub elapsed: Duration,
+    /// Side that wins whatever the other one plays, when the search proved it
+    pub winner: Option<Player>,
+}
+
+impl SearchResult {
+    pub fn best_move(&self) -> Option<&Move> {
+        self.pv.first()
+    }
+
+    /// Whether the search proved that the game is won for one of the sides
+    pub fn is_forced(&self) -> bool {
+        self.winner.is_some()
+    }
+
+    pub fn solution(&self) -> Solution {
+        match self.best_move() {
+            Some(mv) => Solution::Move(mv.clone(), self.score),
+            None => Solution::NoMoves,
+        }
+    }
+}
+
+/// What a single iteration may still spend before it has to stop
+pub(super) struct Budget<'c> {
+    clock: &'c dyn Clock,
+    stop: &'c dyn Stop,
+    deadline: Option<Duration>,
+    nodes: Option<u64>,
+}
+
+impl Budget<'_> {
+    /// Whether the search ran out of time or nodes, or was asked to stop,
+    /// after searching `nodes`
+    pub(super) fn is_spent(&self, nodes: u64) -> bool {
+        if self.nodes.is_some_and(|limit| nodes >= limit) {
+            return true;
+        }
+        if nodes % POLL_INTERVAL != 0 {
+            return false;
+        }
+        self.stop.should_stop()
+            || self
+                .deadline
+                .is_some_and(|deadline| self.clock.now() >= deadline)
+    }
+}
+
+/// Searches one ply deeper at a time until one of the limits is reached, or
+/// `stop` asks to stop. The first iteration always completes, so there is a
+/// move to make whenever the player has one.
+pub fn iterative_deepening<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    variant: &V,
+    limits: &SearchLimits,
+    clock: &dyn Clock,
+    stop: &dyn Stop,
+    table: &mut TranspositionTable,
+) -> SearchResult {
+    let start = clock.now();
+    let deadline = limits.time.map(|time| start + time);
+    let mut result = SearchResult {
+        pv: Vec::new(),
+        score: 0,
+        depth: 0,
+        seldepth: 0,
+        nodes: 0,
+        qnodes: 0,
+        elapsed: Duration::ZERO,
+        winner: None,
+    };
+    table.new_search();
+    // Kept across iterations, so every one starts with what the last one learned
+    let mut order = MoveOrder::default();
+
+    for depth in 1..=limits.max_depth.max(1) {
+        let budget = (depth > 1).then(|| Budget {
+            clock,
+            stop,
+            deadline,
+            nodes: limits.nodes.map(|nodes| nodes.saturating_sub(result.nodes)),
+        });
+        let mut search = Search::new(board, player, variant, Some(&mut *table), depth);
+        search.budget = budget;
+        search.quiescence = limits.quiescence;
+        search.move_ordering = limits.move_ordering;
+        search.pvs = limits.pvs;
+        search.reductions = limits.late_move_reductions;
+        search.order = std::mem::take(&mut order);
+        // Scores of won games jump around between iterations, so they make a
+        // poor guess
+        let guess =
+            (result.depth > 0 && result.winner.is_none()).then(|| for_white(player, result.score));
+        let outcome = match (guess, limits.aspiration_window) {
+            (Some(guess), Some(width)) => aspiration(&mut search, player, depth, guess, width),
+            _ => search.negamax(player, -INFINITY, INFINITY, depth),
+        };
+        order = std::mem::take(&mut search.order);
+        result.nodes += search.nodes;
+        result.qnodes += search.qnodes;
+        if search.stopped {
+            break;
+        }
+        let mut pv = search.pv().to_vec();
+        result.seldepth = search.seldepth;
+        result.depth = depth;
+        result.score = match outcome {
+            Some((score, _)) => for_white(player, score),
+            // The player to move has lost
+            None if player == Player::White => -WIN_SCORE,
+            None => WIN_SCORE,
+        };
+        result.winner = if result.score >= WIN_SCORE {
+            Some(Player::White)
+        } else if result.score <= -WIN_SCORE {
+            Some(Player::Black)
+        } else {
+            None
+        };
+        extend_from_table(board, player, variant, &mut pv, depth as usize, table);
+        result.pv = line_moves(board, &pv);
+
+        let out_of_time = deadline.is_some_and(|deadline| clock.now() >= deadline);
+        let out_of_nodes = limits.nodes.is_some_and(|nodes| result.nodes >= nodes);
+        let stopped = stop.should_stop();
+        if out_of_time || out_of_nodes || stopped || result.pv.is_empty() {
+            break;
+        }
+    }
+    result.elapsed = clock.now().saturating_sub(start);
+    result
+}
+
+/// Searches with a window of `width` around the score guessed for the player,
+/// opening it up on the side the score falls out of until it falls inside
+fn aspiration<V: Variant + ?Sized>(
+    search: &mut Search<V>,
+    player: Player,
+    depth: u8,
+    guess: i32,
+    width: i32,
+) -> Outcome {
+    let mut alpha = guess.saturating_sub(width).max(-INFINITY);
+    let mut beta = guess.saturating_add(width);
+    loop {
+        let outcome = search.negamax(player, alpha, beta, depth);
+        match outcome {
+            _ if search.stopped => return outcome,
+            Some((score, _)) if score <= alpha && alpha > -INFINITY => alpha = -INFINITY,
+            Some((score, _)) if score >= beta && beta < INFINITY => beta = INFINITY,
+            _ => return outcome,
+        }
+    }
+}
+
+/// Moves of the line played out from the board
+fn line_moves(board: &Board, line: &[MoveDelta]) -> Vec<Move> {
+    let mut board = *board;
+    line.iter()
+        .map(|delta| {
+            let mv = Move::new(&board, delta);
+            board.make(delta);
+            mv
+        })
+        .collect()
+}
+
+/// Continues the line up to `len` moves with the best moves the table
+/// remembers, as long as they are available and do not come back to a
+/// position of the line. Lines get cut short where the search found a
+/// position in the table instead of searching it.
+fn extend_from_table<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    variant: &V,
+    line: &mut Vec<MoveDelta>,
+    len: usize,
+    table: &TranspositionTable,
+) {
+    let mut board = *board;
+    let mut player = player;
+    let mut key = ZobristKey::new(&board, player);
+    let mut seen = vec![key];
+    for mv in line.iter() {
+        board.make(mv);
+        key.make(mv);
+        player = player.next_player();
+        seen.push(key);
+    }
+
+    let mut available = Vec::new();
+    while line.len() < len {
+        let mv = match table.probe(key).and_then(|entry| entry.best_move) {
+            Some(mv) => mv,
+            None => break,
+        };
+        available.clear();
+        crate::collect_available_moves(&board, player, variant, &mut available);
+        if !available.contains(&mv) {
+            break;
+        }
+        board.make(&mv);
+        key.make(&mv);
+        player = player.next_player();
+        if seen.contains(&key) {
+            break;
+        }
+        seen.push(key);
+        line.push(mv);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{Board, Cell, Player, Row};
+use super::outcome::{board_outcome, GameOutcome};
+use crate::{Board, Cell, Player, Row, Rules, Variant};
 
 pub trait Evaluate {
     fn evaluate(&self) -> i32;
@@ -16,14 +17,24 @@ impl Evaluate for Row {
     }
 }
 
+/// Evaluation with white to move, as at the start of the game
 impl Evaluate for Board {
     fn evaluate(&self) -> i32 {
-        if !crate::has_moves(self, Player::White) {
-            -200
-        } else if !crate::has_moves(self, Player::Black) {
-            200
-        } else {
-            self.into_iter().map(Evaluate::evaluate).sum()
+        evaluate(self, Player::White, &Rules::default())
+    }
+}
+
+/// Score of a won game from white's point of view. Black wins score the negation.
+pub const WIN_SCORE: i32 = 200;
+
+/// Evaluation of the board when `to_move` is about to move. A side to move
+/// that is out of moves has lost, which depends on the variant.
+pub fn evaluate<V: Variant + ?Sized>(board: &Board, to_move: Player, variant: &V) -> i32 {
+    match board_outcome(board, to_move, variant) {
+        GameOutcome::Win(Player::White, _) => WIN_SCORE,
+        GameOutcome::Win(Player::Black, _) => -WIN_SCORE,
+        GameOutcome::Ongoing | GameOutcome::Draw(_) => {
+            board.into_iter().map(Evaluate::evaluate).sum()
         }
     }
 }
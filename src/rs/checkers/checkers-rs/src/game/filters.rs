@@ -1,5 +1,5 @@
 use crate::Coord;
-use crate::types::{Board, Move, Piece, Player, Position};
+use crate::types::{Board, MoveDelta, Piece, Player, Position};
 
 use super::move_rule::MoveRule;
 use super::rule_seq::RuleSeq;
@@ -14,7 +14,7 @@ impl<H> MoveRule for IfPlayer<H>
 where
     H: MoveRule,
 {
-    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         if board.cell_at(from).is_enemy_to(self.player) {
             None
         } else {
@@ -27,7 +27,7 @@ impl<S> RuleSeq for IfPlayer<S>
 where
     S: RuleSeq,
 {
-    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         if board.cell_at(from).is_enemy_to(self.player) {
             None
         } else {
@@ -46,7 +46,7 @@ impl<H> MoveRule for IfPiece<H>
 where
     H: MoveRule,
 {
-    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         if piece != self.piece {
             None
         } else {
@@ -59,7 +59,7 @@ impl<S> RuleSeq for IfPiece<S>
 where
     S: RuleSeq,
 {
-    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         if piece != self.piece {
             None
         } else {
@@ -79,7 +79,7 @@ where
     H: MoveRule,
     T: Fn(Coord, Coord) -> bool,
 {
-    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         if (self.filter)(from.x, from.y) {
             self.over.compute_move(board, from, piece)
         } else {
@@ -93,7 +93,7 @@ where
     S: RuleSeq,
     T: Fn(&Board, Position, Piece) -> bool,
 {
-    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         if (self.filter)(board, from, piece) {
             self.over.next(board, from, piece)
         } else {
@@ -0,0 +1,177 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Board, Diagonal, MoveDelta, Piece, Player, Position, Variant};
+
+/// Why a move is not legal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalMove {
+    /// The move does not land anywhere
+    EmptyPath,
+    /// There is no piece on the square the move starts from
+    NoPiece,
+    /// The piece belongs to the other player
+    NotYourPiece,
+    /// A hop does not follow a diagonal
+    NotDiagonal,
+    /// A hop lands on an occupied square
+    DestinationOccupied,
+    /// A man tried to go towards its own side of the board
+    MenCannotMoveBackward,
+    /// A simple move was made while the player can eat
+    CaptureIsMandatory,
+    /// The piece stopped while it still has pieces to eat
+    IncompleteCapture,
+    /// Any other breach of the rules, like a man stepping over several
+    /// squares, or an eat taking fewer pieces than the rules demand
+    NotAvailable,
+}
+
+impl Display for IllegalMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IllegalMove::EmptyPath => "move does not land anywhere",
+            IllegalMove::NoPiece => "there is no piece to move",
+            IllegalMove::NotYourPiece => "piece belongs to the other player",
+            IllegalMove::NotDiagonal => "pieces move only along diagonals",
+            IllegalMove::DestinationOccupied => "square is already occupied",
+            IllegalMove::MenCannotMoveBackward => "men cannot move backward",
+            IllegalMove::CaptureIsMandatory => "eating is mandatory",
+            IllegalMove::IncompleteCapture => "piece has more pieces to eat",
+            IllegalMove::NotAvailable => "move is not allowed by the rules",
+        })
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+/// Legal move of the player that starts at `from` and lands on every square of
+/// `path` in order. Otherwise tells what is wrong with the first hop that no
+/// legal move makes.
+pub fn legal_move<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    from: Position,
+    path: &[Position],
+    variant: &V,
+) -> Result<MoveDelta, IllegalMove> {
+    let piece = board
+        .cell_at(from)
+        .into_piece()
+        .ok_or(IllegalMove::NoPiece)?;
+    if piece.player_affiliation() != player {
+        return Err(IllegalMove::NotYourPiece);
+    }
+    if path.is_empty() {
+        return Err(IllegalMove::EmptyPath);
+    }
+
+    let mut moves = Vec::new();
+    crate::collect_available_moves(board, player, variant, &mut moves);
+    let can_eat = moves.iter().any(MoveDelta::is_eat);
+    moves.retain(|mv| mv.from == from);
+    let starts_with = |mv: &MoveDelta, len: usize| {
+        mv.path.len() >= len && mv.path.iter().zip(&path[..len]).all(|(a, b)| a == *b)
+    };
+
+    if let Some(mv) = moves
+        .iter()
+        .find(|mv| mv.path.len() == path.len() && starts_with(mv, path.len()))
+    {
+        return Ok(*mv);
+    }
+    if moves.iter().any(|mv| starts_with(mv, path.len())) {
+        return Err(IllegalMove::IncompleteCapture);
+    }
+
+    let mut start = from;
+    for (idx, &to) in path.iter().enumerate() {
+        if !moves.iter().any(|mv| starts_with(mv, idx + 1)) {
+            let hop = Hop {
+                piece,
+                from,
+                start,
+                to,
+            };
+            return Err(hop.diagnose(board, idx == 0, can_eat, variant));
+        }
+        start = to;
+    }
+    Err(IllegalMove::NotAvailable)
+}
+
+/// Single hop of a move, from `start` to `to`, made by the piece that stood at `from`
+struct Hop {
+    piece: Piece,
+    from: Position,
+    start: Position,
+    to: Position,
+}
+
+impl Hop {
+    fn diagonal(&self) -> Option<Diagonal> {
+        let dx = self.to.x.as_i8() - self.start.x.as_i8();
+        let dy = self.to.y.as_i8() - self.start.y.as_i8();
+        match (dx.signum(), dy.signum()) {
+            _ if dx.abs() != dy.abs() => None,
+            (-1, -1) => Some(Diagonal::UpLeft),
+            (1, -1) => Some(Diagonal::UpRight),
+            (-1, 1) => Some(Diagonal::DownLeft),
+            (1, 1) => Some(Diagonal::DownRight),
+            _ => None,
+        }
+    }
+
+    fn diagnose<V: Variant + ?Sized>(
+        &self,
+        board: &Board,
+        first: bool,
+        can_eat: bool,
+        variant: &V,
+    ) -> IllegalMove {
+        let diagonal = match self.diagonal() {
+            Some(diagonal) => diagonal,
+            None => return IllegalMove::NotDiagonal,
+        };
+        // The square the piece left is free for it to come back to
+        if self.to != self.from && board.is_occupied(self.to) {
+            return IllegalMove::DestinationOccupied;
+        }
+
+        let forward =
+            self.piece.is_queen() || diagonal.is_forward_for(self.piece.player_affiliation());
+        let jumps = self
+            .start
+            .diagonal_between(self.to)
+            .any(|position| position != self.from && board.is_occupied(position));
+        if jumps {
+            if !variant.captures_along(self.piece, diagonal) {
+                return IllegalMove::MenCannotMoveBackward;
+            }
+        } else if first {
+            if can_eat && variant.capture_is_mandatory() {
+                return IllegalMove::CaptureIsMandatory;
+            }
+            if !forward {
+                return IllegalMove::MenCannotMoveBackward;
+            }
+        }
+        IllegalMove::NotAvailable
+    }
+}
+
+impl Board {
+    /// Board after the player moves the piece at `from` along `path`, if that
+    /// is a legal move
+    pub fn try_apply<V: Variant + ?Sized>(
+        &self,
+        player: Player,
+        from: Position,
+        path: &[Position],
+        variant: &V,
+    ) -> Result<Board, IllegalMove> {
+        let mv = legal_move(self, player, from, path, variant)?;
+        let mut board = *self;
+        board.make(&mv);
+        Ok(board)
+    }
+}
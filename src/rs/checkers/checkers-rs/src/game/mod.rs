@@ -1,6 +1,17 @@
 pub mod filters;
+pub mod legality;
+pub mod move_builder;
 pub mod move_rule;
 pub mod moves;
+pub mod notation;
+pub mod outcome;
+pub mod pdn;
 pub mod rule_seq;
+pub mod rules;
+pub mod state;
+pub mod transposition;
+pub mod variant;
+pub mod deepening;
 pub mod evaluate;
 pub mod solution;
+pub mod ordering;
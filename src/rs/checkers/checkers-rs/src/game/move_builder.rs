@@ -0,0 +1,135 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Board, Move, MoveDelta, Player, Position, Variant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HopError {
+    /// No legal move of the piece continues with a hop onto this position
+    IllegalHop(Position),
+    /// Every hop of the move has already been made
+    Complete,
+}
+
+impl Display for HopError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            HopError::IllegalHop(Position { x, y }) => {
+                write!(f, "piece cannot land on ({}, {})", x.as_u8(), y.as_u8())
+            }
+            HopError::Complete => f.write_str("move is already complete"),
+        }
+    }
+}
+
+impl std::error::Error for HopError {}
+
+/// Builds a move of a single piece one hop at a time, the same way a player
+/// clicks through it. Only hops that continue some legal move are accepted.
+#[derive(Debug, Clone)]
+pub struct MoveBuilder {
+    board: Board,
+    from: Position,
+    candidates: Vec<MoveDelta>,
+    path: Vec<Position>,
+}
+
+impl MoveBuilder {
+    /// `None` if there is no piece of the player to move at `from`
+    pub fn new<V: Variant + ?Sized>(
+        board: &Board,
+        player: Player,
+        from: Position,
+        variant: &V,
+    ) -> Option<Self> {
+        if board.cell_at(from).player_affiliation()? != player {
+            return None;
+        }
+        let mut candidates = Vec::new();
+        crate::collect_available_moves(board, player, variant, &mut candidates);
+        candidates.retain(|mv| mv.from == from);
+        Some(MoveBuilder {
+            board: *board,
+            from,
+            candidates,
+            path: Vec::new(),
+        })
+    }
+
+    pub fn from(&self) -> Position {
+        self.from
+    }
+
+    /// Landing squares entered so far
+    pub fn path(&self) -> &[Position] {
+        &self.path
+    }
+
+    /// Whether the piece has any legal move at all
+    pub fn can_move(&self) -> bool {
+        !self.candidates.is_empty()
+    }
+
+    fn matching(&self) -> impl Iterator<Item = &MoveDelta> + '_ {
+        self.candidates
+            .iter()
+            .filter(move |mv| mv.path.iter().zip(&self.path).all(|(a, b)| a == *b))
+    }
+
+    /// Squares the piece may land on with the next hop
+    pub fn next_hops(&self) -> Vec<Position> {
+        let idx = self.path.len();
+        let mut res: Vec<Position> = Vec::new();
+        for hop in self.matching().filter_map(|mv| mv.path.iter().nth(idx)) {
+            if !res.contains(&hop) {
+                res.push(hop);
+            }
+        }
+        res
+    }
+
+    pub fn push(&mut self, to: Position) -> Result<(), HopError> {
+        if self.is_complete() {
+            return Err(HopError::Complete);
+        }
+        if !self.next_hops().contains(&to) {
+            return Err(HopError::IllegalHop(to));
+        }
+        self.path.push(to);
+        Ok(())
+    }
+
+    /// Takes back the last entered hop
+    pub fn pop(&mut self) -> Option<Position> {
+        self.path.pop()
+    }
+
+    fn completed(&self) -> Option<&MoveDelta> {
+        self.matching().find(|mv| mv.path.len() == self.path.len())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed().is_some()
+    }
+
+    /// The move, once every one of its hops is entered
+    pub fn finish(&self) -> Option<Move> {
+        self.completed().map(|mv| Move::new(&self.board, mv))
+    }
+
+    /// Board with the hops entered so far already made: the piece stands on the
+    /// last landing square and the pieces it has jumped over are removed.
+    pub fn current_board(&self) -> Board {
+        let mut board = self.board;
+        let (mv, to) = match (self.matching().next(), self.path.last()) {
+            (Some(mv), Some(&to)) => (mv, to),
+            _ => return board,
+        };
+        let cell = board.cell_at(self.from);
+        board.remove(self.from);
+        for position in mv.captured_in_order().take(self.path.len()) {
+            board.remove(position);
+        }
+        board.replace(to, cell);
+        board
+    }
+}
@@ -2,10 +2,10 @@ use super::{
     filters::{HandlerFilter, IfPiece, IfPlayer},
     rule_seq::{seq, ChainHandler, RuleSeq, SingleRuleSeq},
 };
-use crate::{types::{Board, Move, Piece, Player, Position}, Coord};
+use crate::{types::{Board, MoveDelta, Piece, Player, Position}, Coord};
 
 pub trait MoveRule {
-    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<Move>;
+    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta>;
 
     fn filter_position<P>(self, predicate: P) -> HandlerFilter<Self, P>
     where
@@ -49,9 +49,9 @@ pub trait MoveRule {
 
 impl<T> MoveRule for T
 where
-    T: Fn(&Board, Position, Piece) -> Option<Move>,
+    T: Fn(&Board, Position, Piece) -> Option<MoveDelta>,
 {
-    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         self(board, from, piece)
     }
 }
@@ -60,7 +60,7 @@ impl<T> MoveRule for Option<T>
 where
     T: MoveRule,
 {
-    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn compute_move(&self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         self.as_ref()
             .and_then(|a| a.compute_move(board, from, piece))
     }
@@ -1,13 +1,13 @@
 use crate::{
     c,
-    types::{piece::Piece, Board, Move, Player, Position},
+    types::{piece::Piece, BitBoard, Board, Diagonal, MoveDelta, Path, Player, Position},
     Coord,
 };
-use std::collections::VecDeque;
 
 use super::{
     move_rule::MoveRule,
-    rule_seq::{RuleSeq, RuleSeqIter},
+    rule_seq::{seq, RuleSeq, RuleSeqIter},
+    variant::{MidCapturePromotion, Variant},
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -35,6 +35,40 @@ const fn offset_position(original: Position, direction: Direction2D, offset: u8)
     }
 }
 
+/// Next position along the direction, if it is still on the board
+fn step(original: Position, direction: Direction2D) -> Option<Position> {
+    let x = original.x.as_i8() + direction.x as i8;
+    let y = original.y.as_i8() + direction.y as i8;
+    if (0..8).contains(&x) && (0..8).contains(&y) {
+        Some(offset_position(original, direction, 1))
+    } else {
+        None
+    }
+}
+
+fn eat_delta(
+    board: &Board,
+    from: Position,
+    to: Position,
+    over: Position,
+    piece: Piece,
+) -> Option<MoveDelta> {
+    let captured = BitBoard::square_bit(over)?;
+    Some(MoveDelta {
+        from,
+        to,
+        piece,
+        became: piece.promoted_at(to.y),
+        captured,
+        captured_queens: if board.cell_at(over).queen_bit() == 1 {
+            captured
+        } else {
+            0
+        },
+        path: Path::new(to),
+    })
+}
+
 pub fn eat_handler(x: Direction, y: Direction) -> impl MoveRule + Copy + Clone {
     let direction = Direction2D { x, y };
     return move |board: &Board, from: Position, piece: Piece| {
@@ -49,49 +83,180 @@ pub fn eat_handler(x: Direction, y: Direction) -> impl MoveRule + Copy + Clone {
             return None;
         }
 
-        let mut next_board = board.clone();
-        next_board.move_cell(from, to);
-        next_board.remove(jump_over);
-        Some(Move {
-            from,
-            to,
-            next_board,
-        })
+        eat_delta(board, from, to, jump_over, piece)
     };
 }
 
 pub fn move_handler(x: Direction, y: Direction) -> impl MoveRule + Copy + Clone {
     let direction = Direction2D { x, y };
-    return move |board: &Board, from: Position, _: Piece| {
+    return move |board: &Board, from: Position, piece: Piece| {
         let to = offset_position(from, direction, 1);
         if board.is_occupied(to) {
             return None;
         }
-        let mut next_board = board.clone();
-        next_board.move_cell(from, to);
-        Some(Move {
+        Some(MoveDelta {
             from,
             to,
-            next_board,
+            piece,
+            became: piece.promoted_at(to.y),
+            captured: 0,
+            captured_queens: 0,
+            path: Path::new(to),
         })
     };
 }
 
-pub fn eat_moves(board: &Board, from: Position, piece: Piece) -> RuleSeqIter<'_, impl RuleSeq> {
+/// Simple moves of a flying queen along a single direction: every empty square
+/// up to the first occupied one.
+#[derive(Debug, Clone, Copy)]
+pub struct Slide {
+    direction: Direction2D,
+    last: Option<Position>,
+    done: bool,
+}
+
+pub fn slide(x: Direction, y: Direction) -> Slide {
+    Slide {
+        direction: Direction2D { x, y },
+        last: None,
+        done: false,
+    }
+}
+
+impl RuleSeq for Slide {
+    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
+        if self.done {
+            return None;
+        }
+        let to =
+            step(self.last.unwrap_or(from), self.direction).filter(|to| !board.is_occupied(*to));
+        self.last = to;
+        self.done = to.is_none();
+        to.map(|to| MoveDelta {
+            from,
+            to,
+            piece,
+            became: piece.promoted_at(to.y),
+            captured: 0,
+            captured_queens: 0,
+            path: Path::new(to),
+        })
+    }
+}
+
+/// Eats of a flying queen along a single direction. The queen passes any
+/// number of empty squares, jumps over the first piece on its way if it is an
+/// enemy one, and may land on any empty square behind it.
+#[derive(Debug, Clone, Copy)]
+pub struct FlyingEat {
+    direction: Direction2D,
+    over: Option<Position>,
+    last: Option<Position>,
+    done: bool,
+}
+
+pub fn flying_eat(x: Direction, y: Direction) -> FlyingEat {
+    FlyingEat {
+        direction: Direction2D { x, y },
+        over: None,
+        last: None,
+        done: false,
+    }
+}
+
+impl FlyingEat {
+    fn find_enemy(&self, board: &Board, from: Position, piece: Piece) -> Option<Position> {
+        let mut position = step(from, self.direction)?;
+        while !board.is_occupied(position) {
+            position = step(position, self.direction)?;
+        }
+        Some(position).filter(|over| board.cell_at(*over).is_enemy_to(piece.player_affiliation()))
+    }
+}
+
+impl RuleSeq for FlyingEat {
+    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
+        if self.done {
+            return None;
+        }
+        if self.over.is_none() {
+            self.over = self.find_enemy(board, from, piece);
+        }
+        let to = match self.over {
+            Some(over) => {
+                step(self.last.unwrap_or(over), self.direction).filter(|to| !board.is_occupied(*to))
+            }
+            None => None,
+        };
+        self.last = to;
+        self.done = to.is_none();
+        eat_delta(board, from, to?, self.over?, piece)
+    }
+}
+
+/// Diagonals every kind of piece eats along under a variant. Indexed by
+/// piece, each entry holds a `1 << diagonal` bit per allowed diagonal.
+#[derive(Debug, Clone, Copy)]
+struct CaptureDirections([u8; 4]);
+
+impl CaptureDirections {
+    fn new<V: Variant + ?Sized>(variant: &V) -> Self {
+        let mut res = [0; 4];
+        for piece in [
+            Piece::White,
+            Piece::Black,
+            Piece::WhiteQueen,
+            Piece::BlackQueen,
+        ] {
+            for diagonal in Diagonal::ALL {
+                if variant.captures_along(piece, diagonal) {
+                    res[piece as usize] |= 1 << diagonal as u8;
+                }
+            }
+        }
+        CaptureDirections(res)
+    }
+
+    fn allows(self, piece: Piece, diagonal: Diagonal) -> bool {
+        self.0[piece as usize] & (1 << diagonal as u8) != 0
+    }
+}
+
+pub fn eat_moves<'a, V: Variant + ?Sized>(
+    board: &'a Board,
+    from: Position,
+    piece: Piece,
+    variant: &V,
+) -> RuleSeqIter<'a, impl RuleSeq> {
+    use Diagonal::*;
     use Direction::*;
     let top_left = eat_handler(Neg, Neg).filter_position(|x, y| x > c!(1) && y > c!(1));
     let top_right = eat_handler(Pos, Neg).filter_position(|x, y| x < c!(6) && y > c!(1));
     let bottom_left = eat_handler(Neg, Pos).filter_position(|x, y| x > c!(1) && y < c!(6));
     let bottom_right = eat_handler(Pos, Pos).filter_position(|x, y| x < c!(6) && y < c!(6));
 
-    let top = top_left.chain(top_right);
-    let bottom = bottom_left.chain(bottom_right);
+    let directions = CaptureDirections::new(variant);
+    let along = move |diagonal: Diagonal| {
+        move |_: &Board, _: Position, piece: Piece| directions.allows(piece, diagonal)
+    };
 
-    let rules = top
-        .if_player(Player::White)
-        .chain(bottom.if_player(Player::Black))
-        .chain(bottom.if_piece(Piece::WhiteQueen))
-        .chain(top.if_piece(Piece::BlackQueen));
+    let short = seq(top_left)
+        .filter(along(UpLeft))
+        .chain(seq(top_right).filter(along(UpRight)))
+        .chain(seq(bottom_left).filter(along(DownLeft)))
+        .chain(seq(bottom_right).filter(along(DownRight)));
+
+    let flying = flying_eat(Neg, Neg)
+        .filter(along(UpLeft))
+        .chain(flying_eat(Pos, Neg).filter(along(UpRight)))
+        .chain(flying_eat(Neg, Pos).filter(along(DownLeft)))
+        .chain(flying_eat(Pos, Pos).filter(along(DownRight)));
+
+    let flying_kings = variant.flying_kings();
+    let flies = move |piece: Piece| flying_kings && piece.is_queen();
+    let rules = short
+        .filter(move |_: &Board, _: Position, piece: Piece| !flies(piece))
+        .chain(flying.filter(move |_: &Board, _: Position, piece: Piece| flies(piece)));
 
     RuleSeqIter {
         board,
@@ -101,7 +266,12 @@ pub fn eat_moves(board: &Board, from: Position, piece: Piece) -> RuleSeqIter<'_,
     }
 }
 
-pub fn moves(board: &Board, from: Position, piece: Piece) -> RuleSeqIter<'_, impl RuleSeq> {
+pub fn moves<'a, V: Variant + ?Sized>(
+    board: &'a Board,
+    from: Position,
+    piece: Piece,
+    variant: &V,
+) -> RuleSeqIter<'a, impl RuleSeq> {
     use Direction::*;
     let top_left = move_handler(Neg, Neg).filter_position(|x, y| x > c!(0) && y > c!(0));
     let top_right = move_handler(Pos, Neg).filter_position(|x, y| x < c!(7) && y > c!(0));
@@ -111,12 +281,23 @@ pub fn moves(board: &Board, from: Position, piece: Piece) -> RuleSeqIter<'_, imp
     let top = top_left.chain(top_right);
     let bottom = bottom_left.chain(bottom_right);
 
-    let rules = top
+    let short = top
         .if_player(Player::White)
         .chain(bottom.if_player(Player::Black))
         .chain(bottom.if_piece(Piece::WhiteQueen))
         .chain(top.if_piece(Piece::BlackQueen));
 
+    let flying = slide(Neg, Neg)
+        .chain(slide(Pos, Neg))
+        .chain(slide(Neg, Pos))
+        .chain(slide(Pos, Pos));
+
+    let flying_kings = variant.flying_kings();
+    let flies = move |piece: Piece| flying_kings && piece.is_queen();
+    let rules = short
+        .filter(move |_: &Board, _: Position, piece: Piece| !flies(piece))
+        .chain(flying.filter(move |_: &Board, _: Position, piece: Piece| flies(piece)));
+
     RuleSeqIter {
         board,
         from,
@@ -125,29 +306,76 @@ pub fn moves(board: &Board, from: Position, piece: Piece) -> RuleSeqIter<'_, imp
     }
 }
 
-pub struct ChainEatsIter {
+/// Sets what the piece becomes by the end of the move. Unless the variant lets
+/// men pass through the promotion row, a piece promoted on any landing square
+/// of the move stays promoted.
+pub fn promoted<V: Variant + ?Sized>(variant: &V, mut delta: MoveDelta) -> MoveDelta {
+    delta.became = match variant.mid_capture_promotion() {
+        MidCapturePromotion::PassThrough => variant.promote(delta.piece, delta.to),
+        MidCapturePromotion::EndMove | MidCapturePromotion::ContinueAsQueen => delta
+            .path
+            .iter()
+            .fold(delta.piece, |piece, to| variant.promote(piece, to)),
+    };
+    delta
+}
+
+/// Appends every complete chain of eats of the piece standing at `from` to `out`.
+/// Chains are explored depth-first on copies of the board, so nothing gets
+/// allocated apart from growing `out`.
+pub fn collect_chain_eat_moves<V: Variant + ?Sized>(
+    board: &Board,
+    from: Position,
     piece: Piece,
-    queue: VecDeque<Move>,
-}
-
-impl Iterator for ChainEatsIter {
-    type Item = Move;
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let mv = self.queue.pop_front()?;
-            let prior_len = self.queue.len();
-            self.queue
-                .extend(eat_moves(&mv.next_board, mv.to, self.piece));
-            if self.queue.len() == prior_len {
-                return Some(mv);
-            }
-        }
+    variant: &V,
+    out: &mut Vec<MoveDelta>,
+) {
+    for hop in eat_moves(board, from, piece, variant) {
+        continue_chain(board, hop, piece, variant, out);
     }
 }
 
-pub fn chain_eat_moves(board: &Board, from: Position, piece: Piece) -> ChainEatsIter {
-    ChainEatsIter {
-        piece,
-        queue: eat_moves(board, from, piece).collect(),
+fn continue_chain<V: Variant + ?Sized>(
+    board: &Board,
+    chain: MoveDelta,
+    piece: Piece,
+    variant: &V,
+    out: &mut Vec<MoveDelta>,
+) {
+    // Piece that makes the rest of the chain, if there is any to be made
+    let became = variant.promote(piece, chain.to);
+    let piece = match variant.mid_capture_promotion() {
+        MidCapturePromotion::EndMove if became != piece => {
+            out.push(promoted(variant, chain));
+            return;
+        }
+        MidCapturePromotion::ContinueAsQueen => became,
+        _ => piece,
+    };
+
+    // Eaten pieces are taken off the board only once the whole move is made.
+    // Until then they block the way, and none of them may be jumped twice.
+    let mut next_board = *board;
+    next_board.remove(chain.from);
+    next_board.replace(chain.to, piece.into());
+    let prior_len = out.len();
+    for hop in eat_moves(&next_board, chain.to, piece, variant) {
+        if hop.captured & chain.captured == 0 {
+            continue_chain(board, chain.then(&hop), piece, variant, out);
+        }
+    }
+    if out.len() == prior_len {
+        out.push(promoted(variant, chain));
     }
 }
+
+pub fn chain_eat_moves<V: Variant + ?Sized>(
+    board: &Board,
+    from: Position,
+    piece: Piece,
+    variant: &V,
+) -> std::vec::IntoIter<MoveDelta> {
+    let mut res = Vec::new();
+    collect_chain_eat_moves(board, from, piece, variant, &mut res);
+    res.into_iter()
+}
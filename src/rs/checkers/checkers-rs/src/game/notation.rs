@@ -0,0 +1,122 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Board, Move, Orientation, Player, Position, Square, Variant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotationError {
+    /// Text is not square numbers separated by `-` or `x`
+    Syntax,
+    /// There is no piece on the square the move starts from
+    NoPiece(Square),
+    /// No legal move goes through the squares
+    Illegal,
+    /// More than one legal move goes through the squares, so more of the
+    /// squares it lands on have to be given
+    Ambiguous,
+}
+
+impl Display for NotationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Syntax => {
+                f.write_str("expected square numbers separated by '-' or 'x', like 11-15")
+            }
+            NotationError::NoPiece(square) => write!(f, "there is no piece on square {}", square),
+            NotationError::Illegal => f.write_str("no legal move matches"),
+            NotationError::Ambiguous => {
+                f.write_str("several legal moves match, list the squares they land on")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+fn square_of(position: Position) -> Square {
+    Square::from_position(position, Orientation::default())
+        .expect("pieces stand on dark squares only")
+}
+
+/// Legal moves of the piece on the board that starts the move
+fn moves_of<V: Variant + ?Sized>(
+    board: &Board,
+    from: Square,
+    variant: &V,
+) -> Result<Vec<Move>, NotationError> {
+    crate::moves_for(board, from.into(), variant).ok_or(NotationError::NoPiece(from))
+}
+
+/// Whether the move starts and ends on the first and last of `squares`, and
+/// lands on the ones in between in that order, maybe with others in between
+fn goes_through(mv: &Move, squares: &[Square]) -> bool {
+    let (first, rest) = squares.split_first().expect("at least two squares");
+    let (last, between) = rest.split_last().expect("at least two squares");
+    if square_of(mv.from) != *first || square_of(mv.to) != *last {
+        return false;
+    }
+    let mut landings = mv.path[..mv.path.len() - 1].iter().map(|&p| square_of(p));
+    between
+        .iter()
+        .all(|square| landings.any(|landed| landed == *square))
+}
+
+/// Move in standard notation, like `11-15` or `22x15x8`. Captures list every
+/// square the piece lands on only when other legal moves start and end on
+/// the same squares.
+pub fn format_move<V: Variant + ?Sized>(board: &Board, mv: &Move, variant: &V) -> String {
+    let from = square_of(mv.from);
+    let ends = [from, square_of(mv.to)];
+    let ambiguous = moves_of(board, from, variant)
+        .map(|moves| {
+            moves
+                .iter()
+                .filter(|other| goes_through(other, &ends))
+                .count()
+                > 1
+        })
+        .unwrap_or(false);
+
+    let separator = if mv.captured.is_empty() { "-" } else { "x" };
+    let mut res = from.to_string();
+    if ambiguous {
+        for &landing in &mv.path {
+            res.push_str(separator);
+            res.push_str(&square_of(landing).to_string());
+        }
+    } else {
+        res.push_str(separator);
+        res.push_str(&ends[1].to_string());
+    }
+    res
+}
+
+/// Finds the one legal move of the player written in standard notation.
+/// Either separator is accepted, and the squares in between may be left out as
+/// long as the move stays unique.
+pub fn parse_move<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    text: &str,
+    variant: &V,
+) -> Result<Move, NotationError> {
+    let squares = text
+        .trim()
+        .split(['-', 'x', 'X'])
+        .map(|square| square.parse::<Square>().map_err(|_| NotationError::Syntax))
+        .collect::<Result<Vec<_>, _>>()?;
+    if squares.len() < 2 {
+        return Err(NotationError::Syntax);
+    }
+    if board.cell_at(squares[0].into()).player_affiliation() == Some(player.next_player()) {
+        return Err(NotationError::Illegal);
+    }
+
+    let mut matching = moves_of(board, squares[0], variant)?
+        .into_iter()
+        .filter(|mv| goes_through(mv, &squares));
+    match (matching.next(), matching.next()) {
+        (Some(mv), None) => Ok(mv),
+        (Some(_), Some(_)) => Err(NotationError::Ambiguous),
+        (None, _) => Err(NotationError::Illegal),
+    }
+}
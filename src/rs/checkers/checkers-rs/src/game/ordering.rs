@@ -0,0 +1,82 @@
+use crate::{BitBoard, MoveDelta, Position};
+
+/// Killer moves remembered for every ply
+const KILLERS: usize = 2;
+
+/// Ranks of the groups moves are searched in, from the last group to the first
+const HISTORY_RANK: u64 = 0;
+const KILLER_RANK: u64 = 1 << 32;
+const CAPTURE_RANK: u64 = 2 << 32;
+const HASH_MOVE_RANK: u64 = u64::MAX;
+
+/// What the search learned about which moves cut off, used to search the most
+/// promising moves first. The sooner a good move is searched, the more of the
+/// others alpha-beta gets to prune.
+///
+/// Moves go in this order: the best move the table remembers for the
+/// position, eats by how many pieces they take, the killer moves of the ply,
+/// and the rest by their history.
+#[derive(Debug, Clone)]
+pub struct MoveOrder {
+    /// Simple moves that caused a cutoff at the same ply of another line,
+    /// most recent first
+    killers: Vec<[Option<MoveDelta>; KILLERS]>,
+    /// How much simple moves between two squares caused cutoffs anywhere in
+    /// the search, indexed by the square indices of `BitBoard`
+    history: Box<[[u32; 32]; 32]>,
+}
+
+impl Default for MoveOrder {
+    fn default() -> Self {
+        MoveOrder {
+            killers: Vec::new(),
+            history: Box::new([[0; 32]; 32]),
+        }
+    }
+}
+
+impl MoveOrder {
+    /// Sorts the moves made at the ply, best first. Moves that rank the same
+    /// keep the order they came in.
+    pub fn sort(&self, moves: &mut [MoveDelta], hash_move: Option<MoveDelta>, ply: usize) {
+        moves.sort_by_key(|mv| std::cmp::Reverse(self.rank(mv, hash_move, ply)));
+    }
+
+    fn rank(&self, mv: &MoveDelta, hash_move: Option<MoveDelta>, ply: usize) -> u64 {
+        if hash_move.as_ref() == Some(mv) {
+            return HASH_MOVE_RANK;
+        }
+        if mv.is_eat() {
+            return CAPTURE_RANK + mv.captured_count() as u64;
+        }
+        let mut killers = self.killers.get(ply).into_iter().flatten();
+        if let Some(slot) = killers.position(|killer| killer.as_ref() == Some(mv)) {
+            return KILLER_RANK + (KILLERS - slot) as u64;
+        }
+        HISTORY_RANK + self.history[square(mv.from)][square(mv.to)] as u64
+    }
+
+    /// Remembers that the move made at the ply, searched to the depth, was
+    /// good enough to cut off the rest. Eats already go first, so only simple
+    /// moves are remembered.
+    pub fn cutoff(&mut self, mv: &MoveDelta, ply: usize, depth: u8) {
+        if mv.is_eat() {
+            return;
+        }
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None; KILLERS]);
+        }
+        let killers = &mut self.killers[ply];
+        if killers[0].as_ref() != Some(mv) {
+            killers.rotate_right(1);
+            killers[0] = Some(*mv);
+        }
+        // Cutoffs deeper in the tree prune less, so they count for less
+        let history = &mut self.history[square(mv.from)][square(mv.to)];
+        *history = history.saturating_add(depth as u32 * depth as u32);
+    }
+}
+
+fn square(position: Position) -> usize {
+    BitBoard::square_index(position).expect("moves only go between dark squares") as usize
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{BitBoard, Board, Player, Variant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WinReason {
+    /// The loser has no pieces left
+    #[serde(rename = "noPieces")]
+    NoPieces,
+    /// The loser has pieces, but none of them can move
+    #[serde(rename = "noMoves")]
+    NoMoves,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DrawReason {
+    /// The same position with the same side to move occurred too many times
+    #[serde(rename = "repetition")]
+    Repetition,
+    /// Only queens were moved for too long without eating anything
+    #[serde(rename = "queenMoves")]
+    QueenMoves,
+    /// Both players agreed to a draw
+    #[serde(rename = "agreement")]
+    Agreement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum GameOutcome {
+    #[serde(rename = "ongoing")]
+    Ongoing,
+    #[serde(rename = "win")]
+    Win(Player, WinReason),
+    #[serde(rename = "draw")]
+    Draw(DrawReason),
+}
+
+impl GameOutcome {
+    pub fn is_over(&self) -> bool {
+        *self != GameOutcome::Ongoing
+    }
+}
+
+/// Outcome that follows from the board alone, when `to_move` is about to move.
+/// Draws depend on the history of the game, so they are never reported here.
+pub fn board_outcome<V: Variant + ?Sized>(
+    board: &Board,
+    to_move: Player,
+    variant: &V,
+) -> GameOutcome {
+    let winner = to_move.next_player();
+    if BitBoard::from(board).pieces(to_move) == 0 {
+        GameOutcome::Win(winner, WinReason::NoPieces)
+    } else if !crate::has_moves(board, to_move, variant) {
+        GameOutcome::Win(winner, WinReason::NoMoves)
+    } else {
+        GameOutcome::Ongoing
+    }
+}
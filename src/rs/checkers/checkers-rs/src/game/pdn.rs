@@ -0,0 +1,642 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use super::legality::IllegalMove;
+use super::notation::{format_move, parse_move, NotationError};
+use super::state::Game;
+use crate::{Board, FenError, GameOutcome, Move, Player, Setup, Square, Variant};
+
+/// Place in a PDN text, with lines and columns counted from 1
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Tag pair like `[Event "Club match"]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    pub value: String,
+    pub location: Location,
+}
+
+/// Sequence of moves, either the main line of a game or a variation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveText {
+    /// Comment before the first move
+    pub comment: Option<String>,
+    pub moves: Vec<PdnMove>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdnMove {
+    /// Move in standard notation, like `11-15` or `22x15x8`
+    pub text: String,
+    /// Numeric annotation glyphs, where `!` is written as 1 and `?` as 2
+    pub nags: Vec<u8>,
+    /// Comment after the move
+    pub comment: Option<String>,
+    /// Lines that could have been played instead of this move
+    pub variations: Vec<MoveText>,
+    pub location: Location,
+}
+
+impl PdnMove {
+    pub fn new(text: String) -> Self {
+        PdnMove {
+            text,
+            nags: Vec::new(),
+            comment: None,
+            variations: Vec::new(),
+            location: Location::default(),
+        }
+    }
+}
+
+/// Result token ending the move text. White's score is written first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PdnResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    /// Game is unfinished, or its result is not known
+    #[default]
+    Unknown,
+}
+
+impl PdnResult {
+    /// Both the `2-0` scores of PDN and the `1-0` ones of chess are accepted
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "2-0" | "1-0" => Some(PdnResult::WhiteWins),
+            "0-2" | "0-1" => Some(PdnResult::BlackWins),
+            "1-1" | "1/2-1/2" => Some(PdnResult::Draw),
+            "*" => Some(PdnResult::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl From<GameOutcome> for PdnResult {
+    fn from(outcome: GameOutcome) -> Self {
+        match outcome {
+            GameOutcome::Ongoing => PdnResult::Unknown,
+            GameOutcome::Win(Player::White, _) => PdnResult::WhiteWins,
+            GameOutcome::Win(Player::Black, _) => PdnResult::BlackWins,
+            GameOutcome::Draw(_) => PdnResult::Draw,
+        }
+    }
+}
+
+impl Display for PdnResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PdnResult::WhiteWins => "2-0",
+            PdnResult::BlackWins => "0-2",
+            PdnResult::Draw => "1-1",
+            PdnResult::Unknown => "*",
+        })
+    }
+}
+
+/// Game record read from or written to PDN
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PdnGame {
+    pub tags: Vec<Tag>,
+    pub moves: MoveText,
+    pub result: PdnResult,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdnErrorKind {
+    /// Character that cannot start any token
+    UnexpectedChar(char),
+    /// Token that does not belong where it is, like a `)` without a `(`
+    UnexpectedToken(String),
+    /// Tag pair is not a name and a quoted value in square brackets
+    MalformedTag,
+    /// Comment is missing its closing `}`
+    UnterminatedComment,
+    /// Variation is missing its closing `)`
+    UnclosedVariation,
+    /// Value of the FEN tag is not a valid position
+    Fen(FenError),
+    /// Move does not match exactly one available move
+    Notation(NotationError),
+    /// Move is not legal for the side to move
+    Illegal(IllegalMove),
+    /// Value of the GameType tag is not the variant the game is replayed under
+    GameType(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdnError {
+    pub location: Location,
+    pub kind: PdnErrorKind,
+}
+
+impl Display for PdnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Location { line, column } = self.location;
+        write!(f, "line {}, column {}: ", line, column)?;
+        match &self.kind {
+            PdnErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            PdnErrorKind::UnexpectedToken(token) => write!(f, "unexpected {:?}", token),
+            PdnErrorKind::MalformedTag => f.write_str("expected a tag like [Event \"name\"]"),
+            PdnErrorKind::UnterminatedComment => f.write_str("comment is never closed"),
+            PdnErrorKind::UnclosedVariation => f.write_str("variation is never closed"),
+            PdnErrorKind::Fen(err) => err.fmt(f),
+            PdnErrorKind::Notation(err) => err.fmt(f),
+            PdnErrorKind::Illegal(err) => err.fmt(f),
+            PdnErrorKind::GameType(value) => {
+                write!(f, "game type {:?} is not played by these rules", value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PdnError {}
+
+enum Token {
+    Tag(String, String),
+    Comment(String),
+    Nag(u8),
+    Move(String, Option<u8>),
+    Result(PdnResult),
+    Open,
+    Close,
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    location: Location,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Self {
+        Lexer {
+            chars: text.chars().peekable(),
+            location: Location { line: 1, column: 1 },
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        } else {
+            self.location.column += 1;
+        }
+        Some(c)
+    }
+
+    fn bump_while(&mut self, mut pred: impl FnMut(char) -> bool) -> String {
+        let mut res = String::new();
+        while let Some(&c) = self.chars.peek().filter(|&&c| pred(c)) {
+            res.push(c);
+            self.bump();
+        }
+        res
+    }
+
+    /// Next token and where it starts, skipping whitespace, `%` escape lines
+    /// and move numbers
+    fn next_token(&mut self) -> Result<Option<(Location, Token)>, PdnError> {
+        loop {
+            let at_line_start = self.location.column == 1;
+            let start = self.location;
+            let error = |kind| PdnError {
+                location: start,
+                kind,
+            };
+            let c = match self.bump() {
+                Some(c) => c,
+                None => return Ok(None),
+            };
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                '%' if at_line_start => {
+                    self.bump_while(|c| c != '\n');
+                    continue;
+                }
+                '[' => self
+                    .tag()
+                    .ok_or_else(|| error(PdnErrorKind::MalformedTag))?,
+                '{' => {
+                    let comment = self.bump_while(|c| c != '}');
+                    self.bump()
+                        .ok_or_else(|| error(PdnErrorKind::UnterminatedComment))?;
+                    Token::Comment(comment.trim().to_string())
+                }
+                ';' => Token::Comment(self.bump_while(|c| c != '\n').trim().to_string()),
+                '(' => Token::Open,
+                ')' => Token::Close,
+                '$' => {
+                    let digits = self.bump_while(|c| c.is_ascii_digit());
+                    let nag = digits.parse().map_err(|_| {
+                        error(PdnErrorKind::UnexpectedToken(format!("${}", digits)))
+                    })?;
+                    Token::Nag(nag)
+                }
+                '*' => Token::Result(PdnResult::Unknown),
+                c if is_word_char(c) => {
+                    let mut word = c.to_string();
+                    word.push_str(&self.bump_while(is_word_char));
+                    match word_token(&word) {
+                        Some(Some(token)) => token,
+                        Some(None) => continue,
+                        None => return Err(error(PdnErrorKind::UnexpectedToken(word))),
+                    }
+                }
+                c => return Err(error(PdnErrorKind::UnexpectedChar(c))),
+            };
+            return Ok(Some((start, token)));
+        }
+    }
+
+    /// Rest of a tag pair after its `[`
+    fn tag(&mut self) -> Option<Token> {
+        self.bump_while(char::is_whitespace);
+        let name = self.bump_while(|c| c.is_alphanumeric() || c == '_');
+        self.bump_while(char::is_whitespace);
+        if name.is_empty() || self.bump()? != '"' {
+            return None;
+        }
+        let mut value = String::new();
+        loop {
+            match self.bump()? {
+                '"' => break,
+                '\\' => value.push(self.bump()?),
+                c => value.push(c),
+            }
+        }
+        self.bump_while(char::is_whitespace);
+        if self.bump()? != ']' {
+            return None;
+        }
+        Some(Token::Tag(name, value))
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !"[]{}();$*%".contains(c)
+}
+
+/// Token of a word like `1.`, `11-15!` or `2-0`. `Some(None)` when the word
+/// is only a move number.
+fn word_token(word: &str) -> Option<Option<Token>> {
+    if let Some(result) = PdnResult::from_token(word) {
+        return Some(Some(Token::Result(result)));
+    }
+    let number_len = word
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(word.len());
+    let dots_len = word[number_len..].len() - word[number_len..].trim_start_matches('.').len();
+    let word = if number_len > 0 && dots_len > 0 {
+        &word[number_len + dots_len..]
+    } else {
+        word
+    };
+    if word.is_empty() {
+        return Some(None);
+    }
+
+    let text = word.trim_end_matches(['!', '?']);
+    let nag = match &word[text.len()..] {
+        "" => None,
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => return None,
+    };
+    let is_move = text.contains(['-', 'x', 'X'])
+        && text
+            .chars()
+            .all(|c| c.is_ascii_digit() || "-xX".contains(c));
+    is_move.then(|| Some(Token::Move(text.to_string(), nag)))
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<(Location, Token)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Parser {
+            lexer: Lexer::new(text),
+            peeked: None,
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<&(Location, Token)>, PdnError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next_token()?;
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    fn next(&mut self) -> Result<Option<(Location, Token)>, PdnError> {
+        self.peek()?;
+        Ok(self.peeked.take())
+    }
+
+    /// Next game of the text, or `None` once the text has no more games
+    fn game(&mut self) -> Result<Option<PdnGame>, PdnError> {
+        if self.peek()?.is_none() {
+            return Ok(None);
+        }
+        let mut game = PdnGame::default();
+        while let Some((_, Token::Tag(..))) = self.peek()? {
+            if let Some((location, Token::Tag(name, value))) = self.next()? {
+                game.tags.push(Tag {
+                    name,
+                    value,
+                    location,
+                });
+            }
+        }
+
+        let (moves, result) = self.move_text(None)?;
+        game.moves = moves;
+        game.result = result
+            .or_else(|| game.tag("Result").and_then(PdnResult::from_token))
+            .unwrap_or_default();
+        Ok(Some(game))
+    }
+
+    /// Moves up to the end of the game, or up to the `)` closing the
+    /// variation opened at `opened`
+    fn move_text(
+        &mut self,
+        opened: Option<Location>,
+    ) -> Result<(MoveText, Option<PdnResult>), PdnError> {
+        let mut text = MoveText::default();
+        loop {
+            if let Some((_, Token::Tag(..))) | None = self.peek()? {
+                return match opened {
+                    Some(location) => Err(PdnError {
+                        location,
+                        kind: PdnErrorKind::UnclosedVariation,
+                    }),
+                    None => Ok((text, None)),
+                };
+            }
+            let (location, token) = self.next()?.expect("peeked a token");
+            let unexpected = |token: &str| PdnError {
+                location,
+                kind: PdnErrorKind::UnexpectedToken(token.to_string()),
+            };
+            match token {
+                Token::Tag(..) => unreachable!("tags end the move text"),
+                Token::Comment(comment) => {
+                    let target = match text.moves.last_mut() {
+                        Some(mv) => &mut mv.comment,
+                        None => &mut text.comment,
+                    };
+                    match target {
+                        Some(existing) => {
+                            existing.push(' ');
+                            existing.push_str(&comment);
+                        }
+                        None => *target = Some(comment),
+                    }
+                }
+                Token::Nag(nag) => {
+                    let mv = text.moves.last_mut();
+                    mv.ok_or_else(|| unexpected(&format!("${}", nag)))?
+                        .nags
+                        .push(nag);
+                }
+                Token::Move(move_text, nag) => text.moves.push(PdnMove {
+                    nags: nag.into_iter().collect(),
+                    location,
+                    ..PdnMove::new(move_text)
+                }),
+                Token::Open => {
+                    if text.moves.is_empty() {
+                        return Err(unexpected("("));
+                    }
+                    let (variation, _) = self.move_text(Some(location))?;
+                    let mv = text.moves.last_mut().expect("checked above");
+                    mv.variations.push(variation);
+                }
+                Token::Close if opened.is_some() => return Ok((text, None)),
+                Token::Close => return Err(unexpected(")")),
+                Token::Result(result) if opened.is_none() => return Ok((text, Some(result))),
+                Token::Result(result) => return Err(unexpected(&result.to_string())),
+            }
+        }
+    }
+}
+
+/// Every game of a PDN file
+pub fn parse_pdn(text: &str) -> Result<Vec<PdnGame>, PdnError> {
+    let mut parser = Parser::new(text);
+    let mut games = Vec::new();
+    while let Some(game) = parser.game()? {
+        games.push(game);
+    }
+    Ok(games)
+}
+
+impl FromStr for PdnGame {
+    type Err = PdnError;
+
+    /// Text of exactly one game
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let game = parser.game()?.unwrap_or_default();
+        match parser.next()? {
+            Some((location, _)) => Err(PdnError {
+                location,
+                kind: PdnErrorKind::UnexpectedToken("second game".to_string()),
+            }),
+            None => Ok(game),
+        }
+    }
+}
+
+impl PdnGame {
+    /// Game record of the moves played in the game so far. Gets the `Result`
+    /// tag, the `GameType` tag when the variant has a number, and a `FEN` tag
+    /// unless the game starts from the standard position with black to move.
+    pub fn from_game<V: Variant>(game: &Game<V>) -> Self {
+        let mut res = PdnGame {
+            result: game.outcome().into(),
+            ..PdnGame::default()
+        };
+        res.moves.moves = game
+            .replay()
+            .map(|(board, delta)| {
+                let mv = Move::new(&board, &delta);
+                PdnMove::new(format_move(&board, &mv, game.variant()))
+            })
+            .collect();
+
+        res.set_tag("Result", &res.result.to_string());
+        if let Some(game_type) = game.variant().pdn_game_type() {
+            res.set_tag("GameType", &game_type.to_string());
+        }
+        let start = Setup::new(*game.start(), game.first_player());
+        if start != Setup::new(Board::default(), Player::Black) {
+            res.set_tag("FEN", &start.to_string());
+        }
+        res
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        Some(&self.find_tag(name)?.value)
+    }
+
+    fn find_tag(&self, name: &str) -> Option<&Tag> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
+    /// Replaces the value of the tag, or adds the tag when there is none
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|tag| tag.name == name) {
+            Some(tag) => tag.value = value.to_string(),
+            None => self.tags.push(Tag {
+                name: name.to_string(),
+                value: value.to_string(),
+                location: Location::default(),
+            }),
+        }
+    }
+
+    /// Board and side to move the game starts from. Without a `FEN` tag the
+    /// game starts from the initial board of the variant, and the side owning
+    /// the piece of the first move moves first. That is black for English
+    /// checkers, and white for games played in the web app.
+    ///
+    /// Fails when the `GameType` tag names a variant other than this one.
+    /// Games without the tag are taken to be of this variant.
+    pub fn start<V: Variant + ?Sized>(&self, variant: &V) -> Result<Setup, PdnError> {
+        if let Some(tag) = self.find_tag("GameType") {
+            // The number may be followed by the board layout, as in `21,B,8,8,N1,0`
+            let game_type = tag.value.split(',').next().unwrap_or_default();
+            let game_type = game_type.trim().parse::<u32>().ok();
+            if game_type.is_none() || game_type != variant.pdn_game_type() {
+                return Err(PdnError {
+                    location: tag.location,
+                    kind: PdnErrorKind::GameType(tag.value.clone()),
+                });
+            }
+        }
+        if let Some(tag) = self.find_tag("FEN") {
+            return tag.value.parse().map_err(|err| PdnError {
+                location: tag.location,
+                kind: PdnErrorKind::Fen(err),
+            });
+        }
+        let board = variant.initial_board();
+        let first_square = self.moves.moves.first().and_then(|mv| {
+            let number = mv.text.split(['-', 'x', 'X']).next()?;
+            number.parse::<Square>().ok()
+        });
+        let to_move = first_square
+            .and_then(|square| board.cell_at(square.into()).player_affiliation())
+            .unwrap_or_else(|| variant.first_player());
+        Ok(Setup::new(board, to_move))
+    }
+
+    /// Plays the main line of the game. Variations are checked as well, but
+    /// only the main line ends up in the returned game.
+    pub fn replay<V: Variant + Clone>(&self, variant: V) -> Result<Game<V>, PdnError> {
+        let start = self.start(&variant)?;
+        let mut game = Game::from_position(variant, start.board, start.to_move);
+        play_line(&mut game, &self.moves)?;
+        Ok(game)
+    }
+}
+
+fn play_line<V: Variant + Clone>(game: &mut Game<V>, line: &MoveText) -> Result<(), PdnError> {
+    for pdn_move in &line.moves {
+        for variation in &pdn_move.variations {
+            play_line(&mut game.clone(), variation)?;
+        }
+        let error = |kind| PdnError {
+            location: pdn_move.location,
+            kind,
+        };
+        let mv = parse_move(game.board(), game.to_move(), &pdn_move.text, game.variant())
+            .map_err(|err| error(PdnErrorKind::Notation(err)))?;
+        game.play(&mv)
+            .map_err(|err| error(PdnErrorKind::Illegal(err)))?;
+    }
+    Ok(())
+}
+
+/// Words of the move text, numbering the moves as if the first one was made
+/// at `first_ply`
+fn move_text_words(text: &MoveText, first_ply: usize, words: &mut Vec<String>) {
+    if let Some(comment) = &text.comment {
+        words.push(format!("{{{}}}", comment));
+    }
+    let mut numbered = false;
+    for (idx, mv) in text.moves.iter().enumerate() {
+        let ply = first_ply + idx;
+        if ply % 2 == 0 {
+            words.push(format!("{}.", ply / 2 + 1));
+        } else if !numbered {
+            words.push(format!("{}...", ply / 2 + 1));
+        }
+        numbered = true;
+        words.push(mv.text.clone());
+        words.extend(mv.nags.iter().map(|nag| format!("${}", nag)));
+        if let Some(comment) = &mv.comment {
+            words.push(format!("{{{}}}", comment));
+            numbered = false;
+        }
+        for variation in &mv.variations {
+            let mut variation_words = Vec::new();
+            move_text_words(variation, ply, &mut variation_words);
+            match variation_words.len() {
+                0 => words.push("()".to_string()),
+                len => {
+                    variation_words[0].insert(0, '(');
+                    variation_words[len - 1].push(')');
+                    words.append(&mut variation_words);
+                }
+            }
+            numbered = false;
+        }
+    }
+}
+
+impl Display for PdnGame {
+    /// Tag pairs, then the move text wrapped at 80 columns
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for tag in &self.tags {
+            let value = tag.value.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[{} \"{}\"]", tag.name, value)?;
+        }
+        if !self.tags.is_empty() {
+            writeln!(f)?;
+        }
+
+        let mut words = Vec::new();
+        move_text_words(&self.moves, 0, &mut words);
+        words.push(self.result.to_string());
+        let mut width = 0;
+        for word in words {
+            if width > 0 && width + 1 + word.len() > 80 {
+                writeln!(f)?;
+                width = 0;
+            } else if width > 0 {
+                f.write_str(" ")?;
+                width += 1;
+            }
+            f.write_str(&word)?;
+            width += word.len();
+        }
+        writeln!(f)
+    }
+}
@@ -2,10 +2,10 @@ use super::{
     filters::{HandlerFilter, IfPiece, IfPlayer},
     move_rule::MoveRule,
 };
-use crate::types::{Board, Move, Piece, Player, Position};
+use crate::types::{Board, MoveDelta, Piece, Player, Position};
 
 pub trait RuleSeq {
-    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<Move>;
+    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta>;
 
     fn filter<P>(self, predicate: P) -> HandlerFilter<Self, P>
     where
@@ -56,7 +56,7 @@ impl<T> RuleSeq for SingleRuleSeq<T>
 where
     T: MoveRule,
 {
-    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         self.0.take()?.compute_move(board, from, piece)
     }
 }
@@ -79,7 +79,7 @@ where
     H: RuleSeq,
     N: RuleSeq,
 {
-    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<Move> {
+    fn next(&mut self, board: &Board, from: Position, piece: Piece) -> Option<MoveDelta> {
         match &mut self.current {
             Some(current) => match current.next(board, from, piece) {
                 None => {
@@ -105,7 +105,7 @@ impl<'a, T> Iterator for RuleSeqIter<'a, T>
 where
     T: RuleSeq,
 {
-    type Item = Move;
+    type Item = MoveDelta;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.seq.next(&self.board, self.from, self.piece)
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use super::variant::{MidCapturePromotion, Variant};
+use crate::{Diagonal, MoveDelta, Piece};
+
+/// Switches of the rules of the game. Defaults describe the rules the game has
+/// always been played with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Rules {
+    /// Queens slide any distance along a diagonal, and eat a piece at any
+    /// distance, landing on any empty square beyond it
+    #[serde(rename = "flyingKings", default)]
+    pub flying_kings: bool,
+    /// Men may eat backwards too, while still moving only forwards
+    #[serde(rename = "menCaptureBackwards", default)]
+    pub men_capture_backwards: bool,
+    #[serde(rename = "capturePriority", default)]
+    pub capture_priority: CapturePriority,
+    #[serde(rename = "midCapturePromotion", default)]
+    pub mid_capture_promotion: MidCapturePromotion,
+    /// Occurrences of the same position after which the game is drawn,
+    /// usually 3. Repetitions never draw if not set.
+    #[serde(rename = "repetitionsToDraw", default)]
+    pub repetitions_to_draw: Option<u32>,
+    /// Moves in a row made only by queens without eating, after which the
+    /// game is drawn. Every move of either side counts, so 25 moves each are
+    /// 50 here. Such play never draws if not set.
+    #[serde(rename = "queenMovesToDraw", default)]
+    pub queen_moves_to_draw: Option<u32>,
+}
+
+/// Which of the eats available to a player may be made
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CapturePriority {
+    /// Any eat, as long as something is eaten
+    #[default]
+    #[serde(rename = "any")]
+    Any,
+    /// Only the eats that take the most pieces, as in International and
+    /// Brazilian draughts
+    #[serde(rename = "mostPieces")]
+    MostPieces,
+    /// Italian tie-breaks: the most pieces, then eating with a queen, then
+    /// eating the most queens
+    #[serde(rename = "italian")]
+    Italian,
+}
+
+impl CapturePriority {
+    /// Eats of a higher rank win over the others
+    pub fn rank(self, capture: &MoveDelta) -> u32 {
+        match self {
+            CapturePriority::Any => 0,
+            CapturePriority::MostPieces => capture.captured_count(),
+            CapturePriority::Italian => {
+                (capture.captured_count() << 16)
+                    | ((capture.piece.is_queen() as u32) << 8)
+                    | capture.captured_queen_count()
+            }
+        }
+    }
+}
+
+impl Variant for Rules {
+    fn flying_kings(&self) -> bool {
+        self.flying_kings
+    }
+
+    fn captures_along(&self, piece: Piece, diagonal: Diagonal) -> bool {
+        self.men_capture_backwards
+            || piece.is_queen()
+            || diagonal.is_forward_for(piece.player_affiliation())
+    }
+
+    fn capture_priority(&self, capture: &MoveDelta) -> u32 {
+        self.capture_priority.rank(capture)
+    }
+
+    fn mid_capture_promotion(&self) -> MidCapturePromotion {
+        self.mid_capture_promotion
+    }
+
+    fn queen_moves_to_draw(&self) -> Option<u32> {
+        self.queen_moves_to_draw
+    }
+
+    fn repetitions_to_draw(&self) -> Option<u32> {
+        self.repetitions_to_draw
+    }
+
+    /// Standard variants played on the 8x8 board that the switches add up to
+    fn pdn_game_type(&self) -> Option<u32> {
+        use CapturePriority::*;
+        use MidCapturePromotion::*;
+        let switches = (
+            self.flying_kings,
+            self.men_capture_backwards,
+            self.capture_priority,
+            self.mid_capture_promotion,
+        );
+        match switches {
+            // English draughts
+            (false, false, Any, EndMove) => Some(21),
+            // Pool checkers
+            (true, true, Any, PassThrough) => Some(23),
+            // Russian draughts
+            (true, true, Any, ContinueAsQueen) => Some(25),
+            // Brazilian draughts
+            (true, true, MostPieces, PassThrough) => Some(26),
+            _ => None,
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use std::cmp::{max, min};
 
-use super::evaluate::Evaluate;
-use crate::{Board, Move, Player};
+use super::deepening::Budget;
+use super::evaluate::evaluate;
+use super::ordering::MoveOrder;
+use super::transposition::{Bound, TranspositionTable};
+use crate::{Board, Move, MoveDelta, Player, Variant, ZobristKey};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Solution {
     NoMoves,
     Score(i32),
@@ -11,115 +14,415 @@ pub enum Solution {
 }
 
 impl Solution {
-    fn score(&self) -> Option<i32> {
-        match self {
-            Solution::NoMoves => None,
-            Solution::Score(score) => Some(*score),
-            Solution::Move(_, score) => Some(*score),
+    /// Solution of the root outcome, searched for the player
+    pub(super) fn from_outcome(board: &Board, player: Player, outcome: Outcome) -> Self {
+        match outcome {
+            None => Solution::NoMoves,
+            Some((score, None)) => Solution::Score(for_white(player, score)),
+            Some((score, Some(mv))) => {
+                Solution::Move(Move::new(board, &mv), for_white(player, score))
+            }
         }
     }
+}
+
+/// Score of a searched node for the side to move, together with the best move
+/// out of it. `None` when there are no moves to be made.
+pub(super) type Outcome = Option<(i32, Option<MoveDelta>)>;
 
-    fn new(score: Option<i32>, mv: Option<Move>) -> Self {
-        match (score, mv) {
-            (Some(score), Some(mv)) => Solution::Move(mv, score),
-            (Some(score), None) => Solution::Score(score),
-            _ => Solution::NoMoves,
+/// Bound of every score, which unlike `i32::MIN` can be negated
+pub(super) const INFINITY: i32 = i32::MAX;
+
+/// Turns the score for the player into the one from white's point of view,
+/// and back
+pub(super) fn for_white(player: Player, score: i32) -> i32 {
+    match player {
+        Player::White => score,
+        Player::Black => -score,
+    }
+}
+
+/// How far past its depth a search keeps following eats. Positions with an
+/// eat pending get no static evaluation, since the exchange that follows may
+/// turn the score around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuiescenceLimits {
+    /// Eats in a row followed past the depth. Setting it to 0 turns the
+    /// following off.
+    pub max_ply: u8,
+    /// Nodes a search to one depth may spend following eats. Once they are
+    /// spent, the remaining positions get evaluated as they stand.
+    pub nodes: Option<u64>,
+}
+
+impl Default for QuiescenceLimits {
+    fn default() -> Self {
+        QuiescenceLimits {
+            max_ply: 24,
+            nodes: None,
         }
     }
 }
 
-const LESS: fn(i32, i32) -> bool = |a, b| a < b;
-const GREATER: fn(i32, i32) -> bool = |a, b| a > b;
+/// Moves searched at a node before the later ones may get reduced
+const FULL_DEPTH_MOVES: usize = 3;
+
+/// Remaining depth below which moves are never reduced
+const REDUCTION_MIN_DEPTH: u8 = 3;
 
-pub fn minimax(board: &Board, player: Player, depth: u8) -> Solution {
-    if depth == 0 {
-        return Solution::Score(board.evaluate());
+/// State shared by all nodes of a single search. Moves are made and unmade on
+/// the one board, and every depth gets its own move buffer, which is reused
+/// across nodes instead of allocating a new one each time.
+pub(super) struct Search<'v, V: ?Sized> {
+    board: Board,
+    /// Key of `board` with the side to move, kept in step with it
+    key: ZobristKey,
+    variant: &'v V,
+    table: Option<&'v mut TranspositionTable>,
+    /// Whether moves that cannot change the outcome are cut off. Without
+    /// pruning the search is a plain minimax.
+    pruning: bool,
+    pub(super) quiescence: QuiescenceLimits,
+    /// Whether moves are searched in the order of `order` rather than the
+    /// order they are generated in
+    pub(super) move_ordering: bool,
+    /// Whether moves after the first are searched with a null window, and
+    /// searched again with the full one only when they turn out better
+    pub(super) pvs: bool,
+    /// Whether quiet moves late in the order are searched a ply shallower,
+    /// and searched again to the full depth only when they turn out better
+    pub(super) reductions: bool,
+    pub(super) order: MoveOrder,
+    moves: Vec<Vec<MoveDelta>>,
+    /// Buffers of eats, by ply past the depth
+    captures: Vec<Vec<MoveDelta>>,
+    /// Moves made on `board` since the root of the search
+    ply: usize,
+    /// Best line found from the node at every ply of the current line, where
+    /// each one continues with the line of the next ply
+    pv: Vec<Vec<MoveDelta>>,
+    /// Nodes searched, including those of `qnodes`
+    pub(super) nodes: u64,
+    /// Nodes searched past the depth
+    pub(super) qnodes: u64,
+    /// Deepest ply the search visited
+    pub(super) seldepth: u8,
+    /// Time and nodes the search may spend before it has to stop
+    pub(super) budget: Option<Budget<'v>>,
+    /// Set once the budget is spent. Outcomes of a stopped search mean nothing.
+    pub(super) stopped: bool,
+}
+
+impl<'v, V: Variant + ?Sized> Search<'v, V> {
+    pub(super) fn new(
+        board: &Board,
+        player: Player,
+        variant: &'v V,
+        table: Option<&'v mut TranspositionTable>,
+        depth: u8,
+    ) -> Self {
+        Search {
+            board: *board,
+            key: ZobristKey::new(board, player),
+            variant,
+            table,
+            pruning: true,
+            quiescence: QuiescenceLimits::default(),
+            move_ordering: true,
+            pvs: false,
+            reductions: false,
+            order: MoveOrder::default(),
+            moves: vec![Vec::new(); depth as usize + 1],
+            captures: Vec::new(),
+            ply: 0,
+            pv: vec![Vec::new(); depth as usize + 2],
+            nodes: 0,
+            qnodes: 0,
+            seldepth: 0,
+            budget: None,
+            stopped: false,
+        }
     }
 
-    match player {
-        Player::White => best_move(board, player, depth, GREATER),
-        Player::Black => best_move(board, player, depth, LESS),
+    /// Counts a node being searched, and tells whether the search has to stop
+    fn visit(&mut self) -> bool {
+        self.nodes += 1;
+        self.seldepth = max(self.seldepth, self.ply as u8);
+        if let Some(budget) = &self.budget {
+            self.stopped = self.stopped || budget.is_spent(self.nodes);
+        }
+        self.stopped
     }
-}
 
-fn best_move(
-    board: &Board,
-    player: Player,
-    depth: u8,
-    cmp_fn: impl Fn(i32, i32) -> bool,
-) -> Solution {
-    let moves = crate::available_moves(board, player);
-    let mut score = None;
-    let mut res = None;
-    for mv in moves {
-        let next = minimax(&mv.next_board, player.next_player(), depth - 1);
-        let current_score = next.score().unwrap_or_else(|| mv.next_board.evaluate());
-        if let Some(ref mut score) = score {
-            if cmp_fn(current_score, *score) {
-                *score = current_score;
-                res = Some(mv);
+    /// Best line found from the root
+    pub(super) fn pv(&self) -> &[MoveDelta] {
+        &self.pv[0]
+    }
+
+    /// Sets the line of the current node to `mv`, followed by the line of the
+    /// node after it when `continued`
+    fn update_pv(&mut self, mv: MoveDelta, continued: bool) {
+        if self.pv.len() < self.ply + 2 {
+            self.pv.resize_with(self.ply + 2, Vec::new);
+        }
+        let (lines, next) = self.pv.split_at_mut(self.ply + 1);
+        let line = &mut lines[self.ply];
+        line.clear();
+        line.push(mv);
+        if continued {
+            line.extend_from_slice(&next[0]);
+        }
+    }
+
+    fn clear_pv(&mut self) {
+        if let Some(line) = self.pv.get_mut(self.ply) {
+            line.clear();
+        }
+    }
+
+    fn evaluate(&self, to_move: Player) -> i32 {
+        evaluate(&self.board, to_move, self.variant)
+    }
+
+    fn take_moves(&mut self, player: Player, depth: u8) -> Vec<MoveDelta> {
+        let mut moves = std::mem::take(&mut self.moves[depth as usize]);
+        moves.clear();
+        crate::collect_available_moves(&self.board, player, self.variant, &mut moves);
+        moves
+    }
+
+    fn return_moves(&mut self, depth: u8, moves: Vec<MoveDelta>) {
+        self.moves[depth as usize] = moves;
+    }
+
+    /// Score for the player that made `mv` of the position after it, as
+    /// searched with the window of that player. Falls back to the outcome of the
+    /// game when there are no moves to be made from it.
+    fn child_score(&mut self, mv: &MoveDelta, alpha: i32, beta: i32, depth: u8) -> i32 {
+        let opponent = mv.piece.player_affiliation().next_player();
+        self.board.make(mv);
+        self.key.make(mv);
+        self.ply += 1;
+        let score = match self.negamax(opponent, -beta, -alpha, depth) {
+            Some((score, _)) => score,
+            None => for_white(opponent, self.evaluate(opponent)),
+        };
+        self.ply -= 1;
+        self.key.unmake(mv);
+        self.board.unmake(mv);
+        -score
+    }
+
+    /// Outcome remembered for the position, when it was searched at least as
+    /// deep. Narrows the window by the bound of the remembered score, and
+    /// gives the outcome once it leaves no room to search in.
+    fn probe(&self, depth: u8, alpha: &mut i32, beta: &mut i32) -> Option<Outcome> {
+        let entry = self.table.as_ref()?.probe(self.key)?;
+        if entry.depth < depth {
+            return None;
+        }
+        match entry.bound {
+            Bound::Exact => return Some(Some((entry.score, entry.best_move))),
+            Bound::Lower => *alpha = max(*alpha, entry.score),
+            Bound::Upper => *beta = min(*beta, entry.score),
+        }
+        (*alpha >= *beta).then_some(Some((entry.score, entry.best_move)))
+    }
+
+    /// Best move remembered for the position, however deep it was searched
+    fn hash_move(&self) -> Option<MoveDelta> {
+        self.table.as_ref()?.probe(self.key)?.best_move
+    }
+
+    fn store(&mut self, depth: u8, bound: Bound, outcome: &Outcome) {
+        if self.stopped {
+            return;
+        }
+        if let (Some(table), Some((score, best_move))) = (self.table.as_mut(), outcome) {
+            table.store(self.key, depth, bound, *score, *best_move);
+        }
+    }
+
+    /// Score for the player of a position at the depth of the search. Eats
+    /// are followed until the position is quiet, and only then evaluated.
+    /// Where eating is optional, the player may also stand on the evaluation.
+    fn quiesce(&mut self, player: Player, mut alpha: i32, beta: i32, qply: u8) -> i32 {
+        let standing = for_white(player, self.evaluate(player));
+        let spent = self
+            .quiescence
+            .nodes
+            .is_some_and(|nodes| self.qnodes >= nodes);
+        if qply >= self.quiescence.max_ply || spent || self.stopped {
+            return standing;
+        }
+        let mut captures = self.take_captures(player, qply);
+        if captures.is_empty() {
+            self.return_captures(qply, captures);
+            return standing;
+        }
+        if self.move_ordering {
+            self.order.sort(&mut captures, None, self.ply);
+        }
+
+        let mut best = None;
+        if !self.variant.capture_is_mandatory() {
+            best = Some(standing);
+            alpha = max(alpha, standing);
+        }
+        for mv in &captures {
+            if (self.pruning && alpha >= beta) || self.stopped {
+                break;
+            }
+            let opponent = player.next_player();
+            self.board.make(mv);
+            self.key.make(mv);
+            self.ply += 1;
+            self.qnodes += 1;
+            let score = (!self.visit()).then(|| -self.quiesce(opponent, -beta, -alpha, qply + 1));
+            self.ply -= 1;
+            self.key.unmake(mv);
+            self.board.unmake(mv);
+
+            let Some(score) = score else { break };
+            if best.map_or(true, |best| score > best) {
+                best = Some(score);
+                alpha = max(alpha, score);
             }
-        } else {
-            score = Some(current_score);
-            res = Some(mv);
         }
+        self.return_captures(qply, captures);
+        best.unwrap_or(standing)
+    }
+
+    fn take_captures(&mut self, player: Player, qply: u8) -> Vec<MoveDelta> {
+        if self.captures.len() <= qply as usize {
+            self.captures.resize_with(qply as usize + 1, Vec::new);
+        }
+        let mut captures = std::mem::take(&mut self.captures[qply as usize]);
+        captures.clear();
+        crate::collect_captures(&self.board, player, self.variant, &mut captures);
+        captures
     }
-    Solution::new(score, res)
-}
 
-pub fn alphabeta(board: &Board, player: Player, depth: u8) -> Solution {
-    fn inner(board: &Board, player: Player, mut alpha: i32, mut beta: i32, depth: u8) -> Solution {
+    fn return_captures(&mut self, qply: u8, captures: Vec<MoveDelta>) {
+        self.captures[qply as usize] = captures;
+    }
+
+    /// Score of the move searched as the `idx`-th one at a node of the depth.
+    /// Only the first move is sure to be searched with the full window to the
+    /// full depth. The later ones may first be tried with a null window, or
+    /// shallower, which is enough to tell that they are no better.
+    fn move_score(&mut self, mv: &MoveDelta, idx: usize, alpha: i32, beta: i32, depth: u8) -> i32 {
+        if !self.pruning || idx == 0 {
+            return self.child_score(mv, alpha, beta, depth - 1);
+        }
+        let quiet = !mv.is_eat() && mv.became == mv.piece;
+        if self.reductions && quiet && idx >= FULL_DEPTH_MOVES && depth >= REDUCTION_MIN_DEPTH {
+            let score = self.child_score(mv, alpha, alpha + 1, depth - 2);
+            if score <= alpha || self.stopped {
+                return score;
+            }
+        }
+        if self.pvs {
+            let score = self.child_score(mv, alpha, alpha + 1, depth - 1);
+            if score <= alpha || score >= beta || self.stopped {
+                return score;
+            }
+        }
+        self.child_score(mv, alpha, beta, depth - 1)
+    }
+
+    /// Fail-soft negamax: the score for the player is exact when it falls
+    /// inside the `alpha`..`beta` window. Otherwise it is a bound on the side
+    /// of the window it falls on.
+    pub(super) fn negamax(
+        &mut self,
+        player: Player,
+        mut alpha: i32,
+        mut beta: i32,
+        depth: u8,
+    ) -> Outcome {
+        self.clear_pv();
+        if self.visit() {
+            return None;
+        }
         if depth == 0 {
-            return Solution::Score(board.evaluate());
-        }
-
-        let moves = crate::available_moves(board, player);
-        if player == Player::Black {
-            let mut score = None;
-            let mut res = None;
-            for mv in moves {
-                let next = inner(&mv.next_board, player.next_player(), alpha, beta, depth - 1);
-                let current_score = next.score().unwrap_or_else(|| mv.next_board.evaluate());
-                if let Some(ref mut score) = score {
-                    if current_score < *score {
-                        *score = current_score;
-                        res = Some(mv);
-                        beta = min(beta, current_score);
-                    }
-                } else {
-                    score = Some(current_score);
-                    res = Some(mv);
-                    beta = min(beta, current_score);
-                }
-                if beta <= alpha {
-                    break;
-                }
+            return Some((self.quiesce(player, alpha, beta, 0), None));
+        }
+        let window = (alpha, beta);
+        if let Some(outcome) = self.probe(depth, &mut alpha, &mut beta) {
+            if let Some((_, Some(mv))) = outcome {
+                self.update_pv(mv, false);
             }
-            Solution::new(score, res)
-        } else {
-            let mut score = None;
-            let mut res = None;
-            for mv in moves {
-                let next = inner(&mv.next_board, player.next_player(), alpha, beta, depth - 1);
-                let current_score = next.score().unwrap_or_else(|| mv.next_board.evaluate());
-                if let Some(ref mut score) = score {
-                    if current_score < *score {
-                        *score = current_score;
-                        res = Some(mv);
-                        alpha = max(alpha, current_score);
-                    }
-                } else {
-                    score = Some(current_score);
-                    res = Some(mv);
-                    alpha = max(alpha, current_score);
-                }
-                if alpha >= beta {
-                    break;
+            return outcome;
+        }
+
+        let mut moves = self.take_moves(player, depth);
+        if self.move_ordering {
+            let hash_move = self.hash_move();
+            self.order.sort(&mut moves, hash_move, self.ply);
+        }
+        let mut best: Option<(i32, MoveDelta)> = None;
+        for (idx, mv) in moves.iter().enumerate() {
+            let score = self.move_score(mv, idx, alpha, beta, depth);
+            if best.map_or(true, |(best_score, _)| score > best_score) {
+                best = Some((score, *mv));
+                self.update_pv(*mv, true);
+                alpha = max(alpha, score);
+            }
+            if self.stopped {
+                break;
+            }
+            if self.pruning && alpha >= beta {
+                if self.move_ordering {
+                    self.order.cutoff(mv, self.ply, depth);
                 }
+                break;
             }
-            Solution::new(score, res)
         }
+        self.return_moves(depth, moves);
+        let outcome = best.map(|(score, mv)| (score, Some(mv)));
+        if let Some((score, _)) = outcome {
+            self.store(depth, Bound::of(score, window.0, window.1), &outcome);
+        }
+        outcome
     }
+}
+
+pub fn minimax<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    depth: u8,
+    variant: &V,
+) -> Solution {
+    let mut search = Search::new(board, player, variant, None, depth);
+    search.pruning = false;
+    let outcome = search.negamax(player, -INFINITY, INFINITY, depth);
+    Solution::from_outcome(board, player, outcome)
+}
+
+pub fn alphabeta<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    depth: u8,
+    variant: &V,
+) -> Solution {
+    let mut search = Search::new(board, player, variant, None, depth);
+    let outcome = search.negamax(player, -INFINITY, INFINITY, depth);
+    Solution::from_outcome(board, player, outcome)
+}
 
-    inner(board, player, i32::MIN, i32::MAX, depth)
+/// `alphabeta` that remembers the positions it searched in the table. Reusing
+/// the table for the following moves of a game lets them start from what was
+/// found before, as long as the variant stays the same.
+pub fn alphabeta_with_table<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    depth: u8,
+    variant: &V,
+    table: &mut TranspositionTable,
+) -> Solution {
+    table.new_search();
+    let mut search = Search::new(board, player, variant, Some(table), depth);
+    let outcome = search.negamax(player, -INFINITY, INFINITY, depth);
+    Solution::from_outcome(board, player, outcome)
 }
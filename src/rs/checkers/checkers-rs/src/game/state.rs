@@ -0,0 +1,194 @@
+use super::legality::{legal_move, IllegalMove};
+use super::outcome::{board_outcome, DrawReason, GameOutcome};
+use crate::{Board, Move, MoveDelta, Player, Rules, Variant, ZobristKey};
+
+/// A game in progress: the board, whose turn it is and every move played so
+/// far. Undone moves are kept until a different move is played, so they can
+/// be redone.
+#[derive(Debug, Clone)]
+pub struct Game<V = Rules> {
+    variant: V,
+    start: Board,
+    first_player: Player,
+    board: Board,
+    /// Key of `board` with the side to move, updated move by move
+    key: ZobristKey,
+    moves: Vec<MoveDelta>,
+    /// Key of the position each move of `moves` was played from
+    keys: Vec<ZobristKey>,
+    /// Number of moves of `moves` that are currently made on `board`
+    played: usize,
+    draw_agreed: bool,
+}
+
+impl<V: Variant> Game<V> {
+    pub fn new(variant: V) -> Self {
+        let board = variant.initial_board();
+        let player = variant.first_player();
+        Game::from_position(variant, board, player)
+    }
+
+    /// Game that starts from an arbitrary position
+    pub fn from_position(variant: V, board: Board, to_move: Player) -> Self {
+        Game {
+            variant,
+            start: board,
+            first_player: to_move,
+            board,
+            key: ZobristKey::new(&board, to_move),
+            moves: Vec::new(),
+            keys: Vec::new(),
+            played: 0,
+            draw_agreed: false,
+        }
+    }
+
+    pub fn variant(&self) -> &V {
+        &self.variant
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Zobrist key of the current board and side to move
+    pub fn key(&self) -> ZobristKey {
+        self.key
+    }
+
+    /// Board the game started from
+    pub fn start(&self) -> &Board {
+        &self.start
+    }
+
+    /// Side that made the first move
+    pub fn first_player(&self) -> Player {
+        self.first_player
+    }
+
+    pub fn to_move(&self) -> Player {
+        if self.played % 2 == 0 {
+            self.first_player
+        } else {
+            self.first_player.next_player()
+        }
+    }
+
+    /// Moves played so far, without the undone ones
+    pub fn history(&self) -> &[MoveDelta] {
+        &self.moves[..self.played]
+    }
+
+    pub fn available_moves(&self) -> Vec<Move> {
+        crate::available_moves(&self.board, self.to_move(), &self.variant).collect()
+    }
+
+    /// Plays the move for the side to move. The move has to be one of
+    /// `available_moves`, told apart by where it starts and the squares it
+    /// lands on.
+    pub fn play(&mut self, mv: &Move) -> Result<(), IllegalMove> {
+        let to_move = self.to_move();
+        let delta = legal_move(&self.board, to_move, mv.from, &mv.path, &self.variant)?;
+        self.moves.truncate(self.played);
+        self.moves.push(delta);
+        self.keys.truncate(self.played);
+        self.keys.push(self.key);
+        self.redo();
+        Ok(())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.played > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.played < self.moves.len()
+    }
+
+    /// Takes back the last played move
+    pub fn undo(&mut self) -> Option<MoveDelta> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.played -= 1;
+        self.draw_agreed = false;
+        let mv = self.moves[self.played];
+        self.board.unmake(&mv);
+        self.key.unmake(&mv);
+        Some(mv)
+    }
+
+    /// Plays the last undone move again
+    pub fn redo(&mut self) -> Option<MoveDelta> {
+        let mv = *self.moves.get(self.played)?;
+        self.board.make(&mv);
+        self.key.make(&mv);
+        self.played += 1;
+        Some(mv)
+    }
+
+    /// Ends the game in a draw both players agreed to. Undoing a move takes
+    /// the agreement back.
+    pub fn agree_draw(&mut self) {
+        self.draw_agreed = true;
+    }
+
+    pub fn outcome(&self) -> GameOutcome {
+        if self.draw_agreed {
+            return GameOutcome::Draw(DrawReason::Agreement);
+        }
+        let outcome = board_outcome(&self.board, self.to_move(), &self.variant);
+        if outcome.is_over() {
+            return outcome;
+        }
+        if let Some(limit) = self.variant.queen_moves_to_draw() {
+            let quiet = self.history().iter().rev();
+            let queen_moves = quiet.take_while(|mv| mv.piece.is_queen() && !mv.is_eat());
+            if queen_moves.count() as u32 >= limit {
+                return GameOutcome::Draw(DrawReason::QueenMoves);
+            }
+        }
+        if let Some(limit) = self.variant.repetitions_to_draw() {
+            // Earlier positions with the same side to move are an even number
+            // of moves away. Boards are only compared when the keys match.
+            let boards = self.replay().map(|(board, _)| board);
+            let earlier = self.keys[..self.played].iter().zip(boards);
+            let same_side = earlier.skip(self.played % 2).step_by(2);
+            let repeated =
+                same_side.filter(|(key, board)| **key == self.key && *board == self.board);
+            let repetitions = repeated.count() + 1;
+            if repetitions as u32 >= limit {
+                return GameOutcome::Draw(DrawReason::Repetition);
+            }
+        }
+        GameOutcome::Ongoing
+    }
+
+    /// Goes back to the start of the game. Every move stays available to `redo`.
+    pub fn rewind(&mut self) {
+        while self.undo().is_some() {}
+    }
+
+    /// Replays the game from the start: every played move together with the
+    /// board it was played on
+    pub fn replay(&self) -> impl Iterator<Item = (Board, MoveDelta)> + '_ {
+        self.history().iter().scan(self.start, |board, mv| {
+            let before = *board;
+            board.make(mv);
+            Some((before, *mv))
+        })
+    }
+
+    /// Moves played so far, with the boards they lead to
+    pub fn played_moves(&self) -> Vec<Move> {
+        self.replay()
+            .map(|(board, mv)| Move::new(&board, &mv))
+            .collect()
+    }
+}
+
+impl<V: Variant + Default> Default for Game<V> {
+    fn default() -> Self {
+        Game::new(V::default())
+    }
+}
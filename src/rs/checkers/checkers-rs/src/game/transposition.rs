@@ -0,0 +1,129 @@
+use std::mem::size_of;
+
+use crate::{MoveDelta, ZobristKey};
+
+/// Size of the table used when none is given
+pub const DEFAULT_TABLE_MEGABYTES: usize = 16;
+
+/// How the stored score relates to the real score of the position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bound {
+    /// Every move was searched within the window, so the score is exact
+    Exact,
+    /// The search was cut off, so the real score is at least the stored one
+    Lower,
+    /// No move reached the window, so the real score is at most the stored one
+    Upper,
+}
+
+impl Bound {
+    /// Bound of a score found by searching with the `alpha`..`beta` window
+    pub fn of(score: i32, alpha: i32, beta: i32) -> Self {
+        if score <= alpha {
+            Bound::Upper
+        } else if score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        }
+    }
+}
+
+/// Result of searching a position, as remembered by the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableEntry {
+    pub key: ZobristKey,
+    /// Depth the position was searched to
+    pub depth: u8,
+    pub bound: Bound,
+    pub score: i32,
+    pub best_move: Option<MoveDelta>,
+    /// Search that stored the entry, see `TranspositionTable::new_search`
+    generation: u8,
+}
+
+/// Fixed-size store of search results keyed by position. Every key has one
+/// slot. An entry in it gets replaced by any entry of a later search, and
+/// within the same search by entries searched at least as deep.
+///
+/// Keys tell positions apart but not the variant they were searched under, so
+/// a table used with several variants has to be cleared whenever the variant
+/// changes. The checkers-js wrapper clears its table when the rules change.
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    entries: Vec<Option<TableEntry>>,
+    generation: u8,
+}
+
+impl TranspositionTable {
+    /// Table that takes up at most `megabytes` of memory, but holds at least
+    /// one entry
+    pub fn new(megabytes: usize) -> Self {
+        let fitting = (megabytes.saturating_mul(1 << 20) / size_of::<Option<TableEntry>>()).max(1);
+        // A power of two, so the slot of a key is just its lowest bits
+        let capacity = 1 << fitting.ilog2();
+        TranspositionTable {
+            entries: vec![None; capacity],
+            generation: 0,
+        }
+    }
+
+    /// Number of entries the table can hold
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn slot(&self, key: ZobristKey) -> usize {
+        key.value() as usize & (self.entries.len() - 1)
+    }
+
+    pub fn probe(&self, key: ZobristKey) -> Option<&TableEntry> {
+        self.entries[self.slot(key)]
+            .as_ref()
+            .filter(|entry| entry.key == key)
+    }
+
+    pub fn store(
+        &mut self,
+        key: ZobristKey,
+        depth: u8,
+        bound: Bound,
+        score: i32,
+        best_move: Option<MoveDelta>,
+    ) {
+        let generation = self.generation;
+        let slot = self.slot(key);
+        let replace = match &self.entries[slot] {
+            None => true,
+            Some(entry) => {
+                entry.key == key || entry.generation != generation || entry.depth <= depth
+            }
+        };
+        if replace {
+            self.entries[slot] = Some(TableEntry {
+                key,
+                depth,
+                bound,
+                score,
+                best_move,
+                generation,
+            });
+        }
+    }
+
+    /// Marks the entries stored so far as left by an earlier search. They can
+    /// still be probed, but give way to any entry of the new search.
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        TranspositionTable::new(DEFAULT_TABLE_MEGABYTES)
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, Diagonal, MoveDelta, Piece, Player, Position};
+
+/// What happens to a man that reaches the promotion row in the middle of a
+/// chain of eats, while it still has pieces to eat
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MidCapturePromotion {
+    /// The man is promoted and the move ends there, as in English draughts
+    #[default]
+    #[serde(rename = "endMove")]
+    EndMove,
+    /// The man is promoted and keeps eating as a queen, as in Russian draughts
+    #[serde(rename = "continueAsQueen")]
+    ContinueAsQueen,
+    /// The man keeps eating as a man, and is promoted only if the move ends
+    /// on the promotion row, as in International draughts
+    #[serde(rename = "passThrough")]
+    PassThrough,
+}
+
+/// Rules of a draughts variant, as seen by the move generator, the search and
+/// the game. Every method defaults to the rules the game has always been
+/// played with, so a variant only overrides what it changes.
+pub trait Variant {
+    /// Board the game starts from
+    fn initial_board(&self) -> Board {
+        Board::default()
+    }
+
+    /// Player who makes the first move of the game
+    fn first_player(&self) -> Player {
+        Player::White
+    }
+
+    /// Whether queens are flying kings, moving and eating at any distance
+    fn flying_kings(&self) -> bool {
+        false
+    }
+
+    /// Whether the piece may eat along the diagonal. Men eat only forward,
+    /// queens eat in every direction.
+    fn captures_along(&self, piece: Piece, diagonal: Diagonal) -> bool {
+        piece.is_queen() || diagonal.is_forward_for(piece.player_affiliation())
+    }
+
+    /// Whether a player who can eat is not allowed to make a simple move instead
+    fn capture_is_mandatory(&self) -> bool {
+        true
+    }
+
+    /// Only the eats of the highest priority among every piece of the player
+    /// may be made. All eats are equal by default.
+    fn capture_priority(&self, _capture: &MoveDelta) -> u32 {
+        0
+    }
+
+    /// Piece after landing on the square
+    fn promote(&self, piece: Piece, to: Position) -> Piece {
+        piece.promoted_at(to.y)
+    }
+
+    fn mid_capture_promotion(&self) -> MidCapturePromotion {
+        MidCapturePromotion::EndMove
+    }
+
+    /// Number of moves in a row made by queens only, without eating, after
+    /// which the game is drawn. Moves of both sides count, so these are
+    /// plies rather than full moves. `None` if such play never draws.
+    fn queen_moves_to_draw(&self) -> Option<u32> {
+        None
+    }
+
+    /// Number of times the same position with the same side to move has to
+    /// occur for the game to be drawn. `None` if repetitions never draw.
+    fn repetitions_to_draw(&self) -> Option<u32> {
+        None
+    }
+
+    /// Number of the variant in the `GameType` tag of PDN, 21 being English
+    /// draughts. `None` for variants that have no number of their own.
+    fn pdn_game_type(&self) -> Option<u32> {
+        Some(21)
+    }
+}
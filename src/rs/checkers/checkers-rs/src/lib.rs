@@ -2,99 +2,137 @@ pub mod game;
 pub mod types;
 
 use crate::game::moves::moves;
-use game::moves::{chain_eat_moves, eat_moves};
+use game::moves::{collect_chain_eat_moves, promoted};
+pub use game::deepening::*;
+pub use game::legality::*;
+pub use game::move_builder::*;
+pub use game::notation::*;
+pub use game::outcome::*;
+pub use game::pdn::*;
+pub use game::rules::*;
 pub use game::solution::*;
+pub use game::state::*;
+pub use game::transposition::*;
+pub use game::variant::*;
 pub use types::*;
 
-pub fn moves_for(board: &Board, position: Position) -> Option<Vec<Move>> {
-    let piece = board.cell_at(position).into_piece()?;
-    if player_can_make_eat_move(board, piece.player_affiliation()) {
-        Some(chain_eat_moves(board, position, piece).collect())
-    } else {
-        Some(moves(board, position, piece).collect())
-    }
-}
-
-pub fn has_moves(board: &Board, player: Player) -> bool {
-    player_positions(board, player).any(|(position, piece)| {
-        exists_move_from_position(board, position, piece)
-            || exists_eat_move_from_position(board, position, piece)
-    })
+pub fn moves_for<V: Variant + ?Sized>(
+    board: &Board,
+    position: Position,
+    variant: &V,
+) -> Option<Vec<Move>> {
+    let player = board.cell_at(position).player_affiliation()?;
+    let mut deltas = Vec::new();
+    collect_available_moves(board, player, variant, &mut deltas);
+    let to_move = |delta: &MoveDelta| Move::new(board, delta);
+    Some(deltas.iter().filter(|delta| delta.from == position).map(to_move).collect())
 }
 
-pub fn can_eat(board: &Board, player: Player) -> impl Iterator<Item = Position> + '_ {
-    player_positions(board, player)
-        .filter(move |(position, piece)| exists_eat_move_from_position(board, *position, *piece))
-        .map(|(position, _)| position)
+pub fn has_moves<V: Variant + ?Sized>(board: &Board, player: Player, variant: &V) -> bool {
+    let bits = BitBoard::from(board);
+    bits.movers(player) != 0 || jumpers(&bits, player, variant) != 0
 }
 
-pub fn available_moves(board: &Board, player: Player) -> impl Iterator<Item = Move> + '_ {
-    AvailableMovesIter {
-        had_eats: false,
-        eats: Some(
-            player_positions(board, player)
-                .flat_map(move |(position, piece)| chain_eat_moves(board, position, piece)),
-        ),
-        moves: player_positions(board, player)
-            .flat_map(move |(position, piece)| moves(board, position, piece)),
-    }
+pub fn can_eat<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    variant: &V,
+) -> impl Iterator<Item = Position> {
+    BitBoard::positions(jumpers(&BitBoard::from(board), player, variant))
 }
 
-fn player_positions(board: &Board, player: Player) -> impl Iterator<Item = (Position, Piece)> + '_ {
-    Coord::in_order().zip(board).flat_map(move |(y, row)| {
-        player_row_positions(row, player).map(move |(x, piece)| {
-            let position = Position::new(x, y);
-            (position, piece)
-        })
-    })
+pub fn available_moves<'a, V: Variant + ?Sized>(
+    board: &'a Board,
+    player: Player,
+    variant: &V,
+) -> impl Iterator<Item = Move> + 'a {
+    let mut deltas = Vec::new();
+    collect_available_moves(board, player, variant, &mut deltas);
+    deltas.into_iter().map(move |delta| Move::new(board, &delta))
 }
 
-fn player_row_positions(row: &Row, player: Player) -> impl Iterator<Item = (Coord, Piece)> + '_ {
-    Coord::in_order()
-        .zip(row.into_iter().map(Cell::into_piece))
-        .filter_map(|(x, piece)| piece.map(|piece| (x, piece)))
-        .filter(move |(_, piece)| piece.player_affiliation() == player)
-}
+/// Appends every move the player can make to `out`. Unlike `available_moves`,
+/// this reuses the buffer and does not produce resulting boards.
+pub fn collect_available_moves<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    variant: &V,
+    out: &mut Vec<MoveDelta>,
+) {
+    let bits = BitBoard::from(board);
+    let jumpers = jumpers(&bits, player, variant);
+    collect_eats(board, jumpers, variant, out);
 
-fn player_can_make_eat_move(board: &Board, player: Player) -> bool {
-    player_positions(board, player)
-        .any(|(position, piece)| exists_eat_move_from_position(board, position, piece))
+    // Simple moves are only considered when nobody can eat, unless eating is optional
+    if jumpers == 0 || !variant.capture_is_mandatory() {
+        for (position, piece) in pieces(board, bits.movers(player)) {
+            out.extend(moves(board, position, piece, variant).map(|mv| promoted(variant, mv)));
+        }
+    }
 }
 
-fn exists_eat_move_from_position(board: &Board, from: Position, piece: Piece) -> bool {
-    eat_moves(board, from, piece).next().is_some()
+/// Appends only the eats the player can make to `out`, the way
+/// `collect_available_moves` would find them
+pub fn collect_captures<V: Variant + ?Sized>(
+    board: &Board,
+    player: Player,
+    variant: &V,
+    out: &mut Vec<MoveDelta>,
+) {
+    let jumpers = jumpers(&BitBoard::from(board), player, variant);
+    collect_eats(board, jumpers, variant, out);
 }
 
-fn exists_move_from_position(board: &Board, from: Position, piece: Piece) -> bool {
-    moves(board, from, piece).next().is_some()
+/// Pieces standing on the squares of the mask
+fn pieces(board: &Board, mask: u32) -> impl Iterator<Item = (Position, Piece)> + '_ {
+    BitBoard::positions(mask).filter_map(|position| {
+        board
+            .cell_at(position)
+            .into_piece()
+            .map(|piece| (position, piece))
+    })
 }
 
-struct AvailableMovesIter<E, M> {
-    had_eats: bool,
-    eats: Option<E>,
-    moves: M,
+/// Appends the eats of the highest priority made by the jumpers to `out`
+fn collect_eats<V: Variant + ?Sized>(
+    board: &Board,
+    jumpers: u32,
+    variant: &V,
+    out: &mut Vec<MoveDelta>,
+) {
+    let start = out.len();
+    for (position, piece) in pieces(board, jumpers) {
+        collect_chain_eat_moves(board, position, piece, variant, out);
+    }
+    if out.len() > start {
+        let best = out[start..].iter().map(|mv| variant.capture_priority(mv)).max();
+        let mut idx = 0;
+        out.retain(|mv| {
+            idx += 1;
+            idx <= start || Some(variant.capture_priority(mv)) == best
+        });
+    }
 }
 
-impl<E, M> Iterator for AvailableMovesIter<E, M>
-where
-    E: Iterator<Item = Move>,
-    M: Iterator<Item = Move>,
-{
-    type Item = Move;
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.eats {
-            Some(ref mut eats) => match eats.next() {
-                None => {
-                    self.eats = None;
-                    self.next()
-                }
-                item => {
-                    self.had_eats = true;
-                    item
-                }
-            },
-            None if self.had_eats => None,
-            None => self.moves.next(),
-        }
-    }
+/// Mask of player's pieces that can eat under the variant
+fn jumpers<V: Variant + ?Sized>(bits: &BitBoard, player: Player, variant: &V) -> u32 {
+    let own = bits.pieces(player);
+    let men = own & !bits.kings();
+    let queens = own & bits.kings();
+    Diagonal::ALL.iter().fold(0, |acc, &diagonal| {
+        let along = |queen, mask| {
+            if variant.captures_along(Piece::new(player, queen), diagonal) {
+                mask
+            } else {
+                0
+            }
+        };
+        let (men, queens) = (along(false, men), along(true, queens));
+        let flying = if variant.flying_kings() {
+            bits.flying_jumpers_along(player, queens, diagonal)
+        } else {
+            0
+        };
+        acc | bits.jumpers_along(player, men | queens, diagonal) | flying
+    })
 }
@@ -0,0 +1,270 @@
+use super::{Board, Cell, Piece, Player, Position};
+use crate::Coord;
+
+// Bit `i` of every mask corresponds to the dark square at row `i / 4`, being
+// the `i % 4`-th dark square in that row. Even rows start with a light square,
+// odd rows start with a dark one, which is why shifting a piece one step along
+// a diagonal is either 3, 4 or 5 bits depending on the row parity.
+const EVEN_ROWS: u32 = 0x0F0F_0F0F;
+const ODD_ROWS: u32 = 0xF0F0_F0F0;
+const FIRST_IN_ROW: u32 = 0x1111_1111;
+const LAST_IN_ROW: u32 = 0x8888_8888;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Diagonal {
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Diagonal {
+    pub const ALL: [Diagonal; 4] = [
+        Diagonal::UpLeft,
+        Diagonal::UpRight,
+        Diagonal::DownLeft,
+        Diagonal::DownRight,
+    ];
+
+    /// Directions plain pieces of the player move in. White moves towards row 0,
+    /// black moves towards row 7.
+    pub fn forward(player: Player) -> [Diagonal; 2] {
+        match player {
+            Player::White => [Diagonal::UpLeft, Diagonal::UpRight],
+            Player::Black => [Diagonal::DownLeft, Diagonal::DownRight],
+        }
+    }
+
+    pub fn is_forward_for(self, player: Player) -> bool {
+        Diagonal::forward(player).contains(&self)
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Diagonal::UpLeft => Diagonal::DownRight,
+            Diagonal::UpRight => Diagonal::DownLeft,
+            Diagonal::DownLeft => Diagonal::UpRight,
+            Diagonal::DownRight => Diagonal::UpLeft,
+        }
+    }
+
+    /// Moves every set bit one square along the diagonal. Bits that would leave
+    /// the board are dropped.
+    pub const fn shift(self, bits: u32) -> u32 {
+        match self {
+            Diagonal::UpLeft => {
+                ((bits & EVEN_ROWS) >> 4) | ((bits & ODD_ROWS & !FIRST_IN_ROW) >> 5)
+            }
+            Diagonal::UpRight => {
+                ((bits & EVEN_ROWS & !LAST_IN_ROW) >> 3) | ((bits & ODD_ROWS) >> 4)
+            }
+            Diagonal::DownLeft => {
+                ((bits & EVEN_ROWS) << 4) | ((bits & ODD_ROWS & !FIRST_IN_ROW) << 3)
+            }
+            Diagonal::DownRight => {
+                ((bits & EVEN_ROWS & !LAST_IN_ROW) << 5) | ((bits & ODD_ROWS) << 4)
+            }
+        }
+    }
+}
+
+/// Board representation that stores only the 32 dark squares as bitmasks.
+/// Light squares can never hold a piece, so they are not represented at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BitBoard {
+    white: u32,
+    black: u32,
+    kings: u32,
+}
+
+impl BitBoard {
+    /// Index of the bit that represents position. `None` for light squares
+    pub fn square_index(Position { x, y }: Position) -> Option<u32> {
+        if (x.as_u8() + y.as_u8()) % 2 == 1 {
+            Some((y.as_u8() * 4 + x.as_u8() / 2) as u32)
+        } else {
+            None
+        }
+    }
+
+    pub fn square_bit(position: Position) -> Option<u32> {
+        BitBoard::square_index(position).map(|idx| 1 << idx)
+    }
+
+    pub fn bit_position(idx: u32) -> Position {
+        let y = idx / 4;
+        let x = (idx % 4) * 2 + (y + 1) % 2;
+        // SAFETY: idx is an index of a bit inside of u32, so y is in range 0..8,
+        // and x is at most 3 * 2 + 1 = 7
+        unsafe { Position::new(Coord::new_unchecked(x as u8), Coord::new_unchecked(y as u8)) }
+    }
+
+    /// Iterates positions of every set bit of a mask, row by row.
+    pub fn positions(mut bits: u32) -> impl Iterator<Item = Position> {
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let idx = bits.trailing_zeros();
+            bits &= bits - 1;
+            Some(BitBoard::bit_position(idx))
+        })
+    }
+
+    pub fn pieces(&self, player: Player) -> u32 {
+        match player {
+            Player::White => self.white,
+            Player::Black => self.black,
+        }
+    }
+
+    pub fn kings(&self) -> u32 {
+        self.kings
+    }
+
+    pub fn occupied(&self) -> u32 {
+        self.white | self.black
+    }
+
+    pub fn empty(&self) -> u32 {
+        !self.occupied()
+    }
+
+    pub fn piece_at(&self, position: Position) -> Option<Piece> {
+        let bit = BitBoard::square_bit(position)?;
+        let queen = self.kings & bit != 0;
+        match (self.white & bit != 0, self.black & bit != 0, queen) {
+            (true, _, false) => Some(Piece::White),
+            (true, _, true) => Some(Piece::WhiteQueen),
+            (_, true, false) => Some(Piece::Black),
+            (_, true, true) => Some(Piece::BlackQueen),
+            _ => None,
+        }
+    }
+
+    /// Pieces of the player that are allowed to go along the diagonal
+    fn pieces_going(&self, player: Player, diagonal: Diagonal) -> u32 {
+        if diagonal.is_forward_for(player) {
+            self.pieces(player)
+        } else {
+            self.pieces(player) & self.kings
+        }
+    }
+
+    /// Mask of player's pieces that can make a simple (non-eating) move
+    pub fn movers(&self, player: Player) -> u32 {
+        let empty = self.empty();
+        Diagonal::ALL.iter().fold(0, |acc, &diagonal| {
+            acc | (self.pieces_going(player, diagonal) & diagonal.opposite().shift(empty))
+        })
+    }
+
+    /// Mask of player's pieces that can eat an enemy piece
+    pub fn jumpers(&self, player: Player) -> u32 {
+        Diagonal::ALL.iter().fold(0, |acc, &diagonal| {
+            acc | self.jumpers_along(player, self.pieces_going(player, diagonal), diagonal)
+        })
+    }
+
+    /// Mask of `pieces` that can eat an enemy of the player by jumping along
+    /// the diagonal
+    pub fn jumpers_along(&self, player: Player, pieces: u32, diagonal: Diagonal) -> u32 {
+        let back = diagonal.opposite();
+        let enemy = self.pieces(player.next_player());
+        pieces & back.shift(back.shift(self.empty()) & enemy)
+    }
+
+    /// Mask of player's queens that can eat an enemy piece at any distance,
+    /// like flying kings do
+    pub fn flying_jumpers(&self, player: Player) -> u32 {
+        let queens = self.pieces(player) & self.kings;
+        Diagonal::ALL.iter().fold(0, |acc, &diagonal| {
+            acc | self.flying_jumpers_along(player, queens, diagonal)
+        })
+    }
+
+    /// Mask of `queens` that can eat an enemy of the player at any distance
+    /// along the diagonal
+    pub fn flying_jumpers_along(&self, player: Player, queens: u32, diagonal: Diagonal) -> u32 {
+        let empty = self.empty();
+        let enemy = self.pieces(player.next_player());
+        let back = diagonal.opposite();
+        // Squares right in front of an enemy piece that can be jumped over,
+        // followed by walking back over every empty square before it
+        let mut reach = back.shift(back.shift(empty) & enemy);
+        let mut found = reach & queens;
+        while reach & empty != 0 {
+            reach = back.shift(reach & empty);
+            found |= reach & queens;
+        }
+        found
+    }
+
+    /// Mask of squares reachable with a simple move from the squares of `from`
+    pub fn move_targets(&self, player: Player, from: u32) -> u32 {
+        let empty = self.empty();
+        Diagonal::ALL.iter().fold(0, |acc, &diagonal| {
+            acc | (diagonal.shift(self.pieces_going(player, diagonal) & from) & empty)
+        })
+    }
+
+    /// Mask of squares a piece lands on after eating once from the squares of `from`
+    pub fn jump_targets(&self, player: Player, from: u32) -> u32 {
+        let empty = self.empty();
+        let enemy = self.pieces(player.next_player());
+        Diagonal::ALL.iter().fold(0, |acc, &diagonal| {
+            let over = diagonal.shift(self.pieces_going(player, diagonal) & from) & enemy;
+            acc | (diagonal.shift(over) & empty)
+        })
+    }
+
+    pub fn can_eat(&self, player: Player) -> bool {
+        self.jumpers(player) != 0
+    }
+
+    pub fn has_moves(&self, player: Player) -> bool {
+        self.movers(player) != 0 || self.jumpers(player) != 0
+    }
+}
+
+impl From<&Board> for BitBoard {
+    fn from(board: &Board) -> Self {
+        let mut res = BitBoard::default();
+        for idx in 0..32 {
+            let bit = 1 << idx;
+            match board.cell_at(BitBoard::bit_position(idx)) {
+                Cell::WhitePiece => res.white |= bit,
+                Cell::BlackPiece => res.black |= bit,
+                Cell::WhiteQueen => {
+                    res.white |= bit;
+                    res.kings |= bit;
+                }
+                Cell::BlackQueen => {
+                    res.black |= bit;
+                    res.kings |= bit;
+                }
+                Cell::White | Cell::Black => {}
+            }
+        }
+        res
+    }
+}
+
+impl From<Board> for BitBoard {
+    fn from(board: Board) -> Self {
+        BitBoard::from(&board)
+    }
+}
+
+impl From<BitBoard> for Board {
+    fn from(bits: BitBoard) -> Self {
+        let mut board = Board::empty();
+        for idx in 0..32 {
+            let position = BitBoard::bit_position(idx);
+            if let Some(piece) = bits.piece_at(position) {
+                board.replace(position, piece.into());
+            }
+        }
+        board
+    }
+}
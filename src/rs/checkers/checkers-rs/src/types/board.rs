@@ -1,16 +1,24 @@
 use crate::Coord;
 
-use super::{promote, Cell, Position, Row};
+use super::{promote, BitBoard, Cell, MoveDelta, Orientation, Piece, Position, Row, Square};
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::{Display, Formatter, Write},
     ops::{Index, IndexMut},
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Board([Row; 8]);
 
 impl Board {
+    /// Board without any pieces on it
+    pub fn empty() -> Self {
+        use Cell::{Black, White};
+        let even = Row::from([White, Black, White, Black, White, Black, White, Black]);
+        let odd = Row::from([Black, White, Black, White, Black, White, Black, White]);
+        Board([even, odd, even, odd, even, odd, even, odd])
+    }
+
     pub fn cell_at(&self, Position { x, y }: Position) -> Cell {
         self[y].cell_at(x)
     }
@@ -30,9 +38,36 @@ impl Board {
         self.remove(from);
     }
 
+    /// Cells of the dark squares, in the order of their numbers
+    pub fn squares(&self, orientation: Orientation) -> impl Iterator<Item = (Square, Cell)> {
+        let board = *self;
+        Square::all().map(move |square| (square, board.cell_at(square.position(orientation))))
+    }
+
     pub fn is_occupied(&self, pos: Position) -> bool {
         self.cell_at(pos).is_piece()
     }
+
+    pub fn make(&mut self, delta: &MoveDelta) {
+        self.remove(delta.from);
+        for position in BitBoard::positions(delta.captured) {
+            self.remove(position);
+        }
+        self.replace(delta.to, delta.became.into());
+    }
+
+    /// Takes back a move previously applied with `make`
+    pub fn unmake(&mut self, delta: &MoveDelta) {
+        self.remove(delta.to);
+        let enemy = delta.piece.player_affiliation().next_player();
+        for position in BitBoard::positions(delta.captured & !delta.captured_queens) {
+            self.replace(position, Piece::new(enemy, false).into());
+        }
+        for position in BitBoard::positions(delta.captured_queens) {
+            self.replace(position, Piece::new(enemy, true).into());
+        }
+        self.replace(delta.from, delta.piece.into());
+    }
 }
 
 impl Index<Coord> for Board {
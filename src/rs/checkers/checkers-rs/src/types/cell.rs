@@ -82,6 +82,15 @@ impl Cell {
     }
 }
 
+impl From<Piece> for Cell {
+    fn from(piece: Piece) -> Self {
+        // SAFETY: Piece values are exactly the color and rank bits of Cell.
+        // Setting the piece bit on top of them yields one of WhitePiece,
+        // BlackPiece, WhiteQueen or BlackQueen
+        unsafe { Cell::from_unchecked(piece as u8 | 0b100) }
+    }
+}
+
 pub fn promote(y: Coord, cell: Cell) -> Cell {
     match (y.as_u8(), cell) {
         (7, Cell::BlackPiece) => Cell::BlackQueen,
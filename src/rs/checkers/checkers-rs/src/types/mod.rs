@@ -1,3 +1,4 @@
+pub mod bitboard;
 pub mod board;
 pub mod cell;
 pub mod move_type;
@@ -5,9 +6,13 @@ pub mod piece;
 pub mod player;
 pub mod position;
 pub mod row;
+pub mod setup;
 pub mod sizes;
+pub mod square;
+pub mod zobrist;
 pub mod coord;
 
+pub use bitboard::*;
 pub use board::*;
 pub use cell::*;
 pub use move_type::*;
@@ -15,5 +20,8 @@ pub use piece::*;
 pub use player::*;
 pub use position::*;
 pub use row::*;
+pub use setup::*;
 pub use sizes::*;
+pub use square::*;
+pub use zobrist::*;
 pub use coord::*;
@@ -1,10 +1,130 @@
-use super::{board::Board, position::Position};
+use super::{bitboard::BitBoard, board::Board, piece::Piece, position::Position};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Move {
     pub from: Position,
     pub to: Position,
+    /// Squares the piece lands on after every hop, ending with `to`
+    pub path: Vec<Position>,
+    /// Positions of eaten pieces, in the order they were eaten
+    pub captured: Vec<Position>,
     #[serde(rename = "nextBoard")]
     pub next_board: Board,
 }
+
+impl Move {
+    pub fn new(board: &Board, delta: &MoveDelta) -> Self {
+        let mut next_board = *board;
+        next_board.make(delta);
+        Move {
+            from: delta.from,
+            to: delta.to,
+            path: delta.path.iter().collect(),
+            captured: delta.captured_in_order().collect(),
+            next_board,
+        }
+    }
+}
+
+/// Most hops a single move can be made of: one for every piece of the opponent
+pub const MAX_HOPS: usize = 12;
+
+/// Landing squares of every hop of a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Path {
+    len: u8,
+    // Indices of dark squares, as used by BitBoard
+    squares: [u8; MAX_HOPS],
+}
+
+impl Path {
+    pub fn new(to: Position) -> Self {
+        let mut path = Path {
+            len: 0,
+            squares: [0; MAX_HOPS],
+        };
+        path.push(to);
+        path
+    }
+
+    pub fn push(&mut self, position: Position) {
+        let idx = BitBoard::square_index(position).expect("pieces only stand on dark squares");
+        self.squares[self.len as usize] = idx as u8;
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Position> + '_ {
+        self.squares[..self.len()]
+            .iter()
+            .map(|&idx| BitBoard::bit_position(idx as u32))
+    }
+}
+
+/// Squares changed by a move. Holds just enough to apply the move onto a board
+/// and to take it back, without carrying a copy of the resulting board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveDelta {
+    pub from: Position,
+    pub to: Position,
+    /// Piece that is being moved
+    pub piece: Piece,
+    /// Piece that ends up on `to`. Differs from `piece` when it gets promoted
+    pub became: Piece,
+    /// Dark-square mask (see `BitBoard`) of eaten pieces
+    pub captured: u32,
+    /// Which of the eaten pieces were queens
+    pub captured_queens: u32,
+    pub path: Path,
+}
+
+impl MoveDelta {
+    pub fn is_eat(&self) -> bool {
+        self.captured != 0
+    }
+
+    pub fn captured_count(&self) -> u32 {
+        self.captured.count_ones()
+    }
+
+    pub fn captured_queen_count(&self) -> u32 {
+        self.captured_queens.count_ones()
+    }
+
+    /// Continues a chain of eats with the next hop, that starts where this one ends
+    pub fn then(&self, hop: &MoveDelta) -> Self {
+        let mut path = self.path;
+        path.push(hop.to);
+        MoveDelta {
+            from: self.from,
+            to: hop.to,
+            piece: self.piece,
+            became: if self.became.is_queen() {
+                self.became
+            } else {
+                hop.became
+            },
+            captured: self.captured | hop.captured,
+            captured_queens: self.captured_queens | hop.captured_queens,
+            path,
+        }
+    }
+
+    /// Positions of eaten pieces, hop by hop
+    pub fn captured_in_order(&self) -> impl Iterator<Item = Position> + '_ {
+        let starts = std::iter::once(self.from).chain(self.path.iter());
+        starts.zip(self.path.iter()).flat_map(move |(start, end)| {
+            start.diagonal_between(end).filter(move |&position| {
+                BitBoard::square_bit(position).is_some_and(|bit| self.captured & bit != 0)
+            })
+        })
+    }
+}
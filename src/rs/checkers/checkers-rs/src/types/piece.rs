@@ -1,5 +1,7 @@
 use num_enum::UnsafeFromPrimitive;
 
+use crate::Coord;
+
 use super::Player;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, UnsafeFromPrimitive)]
@@ -12,6 +14,15 @@ pub enum Piece {
 }
 
 impl Piece {
+    pub fn new(player: Player, queen: bool) -> Self {
+        match (player, queen) {
+            (Player::White, false) => Piece::White,
+            (Player::Black, false) => Piece::Black,
+            (Player::White, true) => Piece::WhiteQueen,
+            (Player::Black, true) => Piece::BlackQueen,
+        }
+    }
+
     pub fn player_affiliation(&self) -> Player {
         // SAFETY: First bit of Piece enum value represents color affiliation
         // exactly the same way Player enum does (White = 0, Black = 1).
@@ -23,4 +34,13 @@ impl Piece {
     pub fn is_queen(&self) -> bool {
         ((*self as u8) >> 1) & 1 == 1
     }
+
+    /// Piece after landing on row `y`
+    pub fn promoted_at(self, y: Coord) -> Self {
+        match (y.as_u8(), self) {
+            (7, Piece::Black) => Piece::BlackQueen,
+            (0, Piece::White) => Piece::WhiteQueen,
+            _ => self,
+        }
+    }
 }
@@ -2,7 +2,17 @@ use num_enum::UnsafeFromPrimitive;
 use serde::{Deserialize, Serialize};
 
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, UnsafeFromPrimitive, Serialize, Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    UnsafeFromPrimitive,
+    Serialize,
+    Deserialize,
 )]
 #[repr(u8)]
 pub enum Player {
@@ -33,6 +33,21 @@ impl Position {
     pub fn new(x: Coord, y: Coord) -> Self {
         Self { x, y }
     }
+
+    /// Positions strictly between this one and `other`, walking from this one.
+    /// Empty if the two don't lie on the same diagonal.
+    pub fn diagonal_between(self, other: Position) -> impl Iterator<Item = Position> {
+        let dx = other.x.as_i8() - self.x.as_i8();
+        let dy = other.y.as_i8() - self.y.as_i8();
+        let steps = if dx.abs() == dy.abs() { dx.abs() } else { 0 };
+        (1..steps).map(move |step| {
+            let x = self.x.as_i8() + dx.signum() * step;
+            let y = self.y.as_i8() + dy.signum() * step;
+            // SAFETY: Every step is strictly between two valid positions on a
+            // diagonal, so both coordinates stay in range 0..8
+            unsafe { Position::new(Coord::new_unchecked(x as u8), Coord::new_unchecked(y as u8)) }
+        })
+    }
 }
 
 impl Serialize for Position {
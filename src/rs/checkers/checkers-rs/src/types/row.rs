@@ -9,7 +9,7 @@ use serde::{
     Deserialize, Deserializer, Serialize,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Row(u32);
 
 impl Row {
@@ -53,33 +53,30 @@ impl From<[Cell; 8]> for Row {
 
 pub struct RowIter {
     row: Row,
-    idx: Coord,
+    idx: Option<Coord>,
 }
 
 impl Iterator for RowIter {
     type Item = Cell;
     fn next(&mut self) -> Option<Self::Item> {
-        let prev = self.idx;
-        if let Some(next) = self.idx.forward() {
-            self.idx = next;
-            Some(self.row.cell_at(prev))
-        } else {
-            None
-        }
+        let idx = self.idx?;
+        self.idx = idx.forward();
+        Some(self.row.cell_at(idx))
     }
-}
 
-impl ExactSizeIterator for RowIter {
-    fn len(&self) -> usize {
-        8
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.idx.map_or(0, |idx| 8 - idx.as_usize());
+        (len, Some(len))
     }
 }
 
+impl ExactSizeIterator for RowIter {}
+
 impl IntoIterator for Row {
     type Item = Cell;
     type IntoIter = RowIter;
     fn into_iter(self) -> Self::IntoIter {
-        RowIter { row: self, idx: c!(0) }
+        RowIter { row: self, idx: Some(c!(0)) }
     }
 }
 
@@ -87,7 +84,7 @@ impl IntoIterator for &Row {
     type Item = Cell;
     type IntoIter = RowIter;
     fn into_iter(self) -> Self::IntoIter {
-        RowIter { row: *self, idx: c!(0) }
+        RowIter { row: *self, idx: Some(c!(0)) }
     }
 }
 
@@ -0,0 +1,160 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Orientation, Piece, Player, Square};
+
+/// Board together with the side to move. Written as a PDN FEN string, like
+/// `W:W21,22,K30:B1,2,3`: the side to move, then the squares of white and
+/// black pieces, where `K` marks queens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setup {
+    pub board: Board,
+    #[serde(rename = "toMove")]
+    pub to_move: Player,
+}
+
+impl Setup {
+    pub fn new(board: Board, to_move: Player) -> Self {
+        Setup { board, to_move }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FenError {
+    /// Text is not three parts separated by `:`
+    Sections,
+    /// Side to move or piece list does not start with `W` or `B`
+    Player,
+    /// Piece lists are not one list of white pieces and one of black pieces
+    Lists,
+    /// Piece list entry is not a square number or a range like `1-12`
+    Square,
+    /// Range of squares ends before it starts, like `12-1`
+    Range,
+    /// Square has more than one piece on it
+    Occupied(Square),
+}
+
+impl Display for FenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::Sections => f.write_str(
+                "expected side to move and two piece lists separated by ':', like W:W21,22:B1,2",
+            ),
+            FenError::Player => f.write_str("players are written as 'W' or 'B'"),
+            FenError::Lists => f.write_str("expected one list of white pieces and one of black"),
+            FenError::Square => f.write_str("pieces stand on squares numbered from 1 to 32"),
+            FenError::Range => f.write_str("range of squares ends before it starts"),
+            FenError::Occupied(square) => write!(f, "square {} is listed more than once", square),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+fn player_of(letter: char) -> Result<Player, FenError> {
+    match letter.to_ascii_uppercase() {
+        'W' => Ok(Player::White),
+        'B' => Ok(Player::Black),
+        _ => Err(FenError::Player),
+    }
+}
+
+fn letter_of(player: Player) -> char {
+    match player {
+        Player::White => 'W',
+        Player::Black => 'B',
+    }
+}
+
+/// Puts the pieces of a list like `W21,22,K30` or `B1-12` on the board, and
+/// tells whose pieces they are
+fn place_pieces(board: &mut Board, list: &str) -> Result<Player, FenError> {
+    let mut chars = list.chars();
+    let player = player_of(chars.next().ok_or(FenError::Player)?)?;
+    let entries = chars.as_str().split(',').map(str::trim);
+    for entry in entries.filter(|entry| !entry.is_empty()) {
+        let (queen, squares) = match entry.strip_prefix(['K', 'k']) {
+            Some(squares) => (true, squares),
+            None => (false, entry),
+        };
+        let parse = |number: &str| {
+            number
+                .trim()
+                .parse::<Square>()
+                .map_err(|_| FenError::Square)
+        };
+        let (first, last) = match squares.split_once('-') {
+            Some((first, last)) => (parse(first)?, parse(last)?),
+            None => (parse(squares)?, parse(squares)?),
+        };
+        if first > last {
+            return Err(FenError::Range);
+        }
+        for number in first.number()..=last.number() {
+            let square = Square::new(number).expect("between two valid squares");
+            let position = square.position(Orientation::default());
+            if board.is_occupied(position) {
+                return Err(FenError::Occupied(square));
+            }
+            board.replace(position, Piece::new(player, queen).into());
+        }
+    }
+    Ok(player)
+}
+
+impl FromStr for Setup {
+    type Err = FenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_end_matches('.');
+        let mut sections = s.split(':').map(str::trim);
+        let (turn, first, second) = match (sections.next(), sections.next(), sections.next()) {
+            (Some(turn), Some(first), Some(second)) if sections.next().is_none() => {
+                (turn, first, second)
+            }
+            _ => return Err(FenError::Sections),
+        };
+
+        let mut turn_chars = turn.chars();
+        let to_move = match (turn_chars.next(), turn_chars.next()) {
+            (Some(letter), None) => player_of(letter)?,
+            _ => return Err(FenError::Player),
+        };
+        let mut board = Board::empty();
+        let first = place_pieces(&mut board, first)?;
+        let second = place_pieces(&mut board, second)?;
+        if first == second {
+            return Err(FenError::Lists);
+        }
+        Ok(Setup { board, to_move })
+    }
+}
+
+impl Display for Setup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", letter_of(self.to_move))?;
+        for player in [Player::White, Player::Black] {
+            write!(f, ":{}", letter_of(player))?;
+            let pieces = self
+                .board
+                .squares(Orientation::default())
+                .filter_map(|(square, cell)| {
+                    let piece = cell.into_piece()?;
+                    (piece.player_affiliation() == player).then_some((square, piece.is_queen()))
+                });
+            for (idx, (square, queen)) in pieces.enumerate() {
+                if idx > 0 {
+                    f.write_str(",")?;
+                }
+                if queen {
+                    f.write_str("K")?;
+                }
+                write!(f, "{}", square)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{BitBoard, Position};
+use crate::Coord;
+
+/// Where the numbering of dark squares starts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Orientation {
+    /// Square 1 is in black's back row, so black starts on squares 1 to 12.
+    /// The numbering of checkers literature.
+    #[default]
+    #[serde(rename = "blackFirst")]
+    BlackFirst,
+    /// The board turned around, so white starts on squares 1 to 12
+    #[serde(rename = "whiteFirst")]
+    WhiteFirst,
+}
+
+/// Dark square in standard numbering from 1 to 32. Squares are numbered row by
+/// row, left to right, starting from the row at the top of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct Square(u8);
+
+impl Square {
+    pub fn new(number: u8) -> Option<Self> {
+        if (1..=32).contains(&number) {
+            Some(Square(number))
+        } else {
+            None
+        }
+    }
+
+    pub fn number(self) -> u8 {
+        self.0
+    }
+
+    /// Every square from 1 to 32
+    pub fn all() -> impl Iterator<Item = Square> {
+        (1..=32).map(Square)
+    }
+
+    /// `None` for light squares
+    pub fn from_position(position: Position, orientation: Orientation) -> Option<Self> {
+        let idx = BitBoard::square_index(orientation.turn(position))?;
+        Some(Square(idx as u8 + 1))
+    }
+
+    pub fn position(self, orientation: Orientation) -> Position {
+        orientation.turn(BitBoard::bit_position(self.0 as u32 - 1))
+    }
+}
+
+impl Orientation {
+    /// Turns the position of `BlackFirst` numbering into the one of this
+    /// orientation and back
+    fn turn(self, position: Position) -> Position {
+        match self {
+            Orientation::BlackFirst => position,
+            // SAFETY: Both coordinates are in range 0..8, and so are their
+            // reflections
+            Orientation::WhiteFirst => unsafe {
+                Position::new(
+                    Coord::new_unchecked(7 - position.x.as_u8()),
+                    Coord::new_unchecked(7 - position.y.as_u8()),
+                )
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidSquare;
+
+impl Display for InvalidSquare {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("square must be a dark square numbered from 1 to 32")
+    }
+}
+
+impl std::error::Error for InvalidSquare {}
+
+impl TryFrom<u8> for Square {
+    type Error = InvalidSquare;
+
+    fn try_from(number: u8) -> Result<Self, Self::Error> {
+        Square::new(number).ok_or(InvalidSquare)
+    }
+}
+
+impl From<Square> for u8 {
+    fn from(square: Square) -> Self {
+        square.0
+    }
+}
+
+impl TryFrom<Position> for Square {
+    type Error = InvalidSquare;
+
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        Square::from_position(position, Orientation::default()).ok_or(InvalidSquare)
+    }
+}
+
+impl From<Square> for Position {
+    fn from(square: Square) -> Self {
+        square.position(Orientation::default())
+    }
+}
+
+impl FromStr for Square {
+    type Err = InvalidSquare;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<u8>()
+            .ok()
+            .and_then(Square::new)
+            .ok_or(InvalidSquare)
+    }
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
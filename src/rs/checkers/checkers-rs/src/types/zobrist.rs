@@ -0,0 +1,75 @@
+use super::{BitBoard, Board, MoveDelta, Piece, Player, Position};
+
+/// Output of the splitmix64 generator at `state`. Being a `const fn` of plain
+/// integer arithmetic, it gives the same keys on native and wasm builds.
+const fn splitmix(state: u64) -> u64 {
+    let mut z = state.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Key of every piece on every dark square, indexed by `Piece` and by the
+/// square index of `BitBoard`
+const PIECE_KEYS: [[u64; 32]; 4] = {
+    let mut keys = [[0; 32]; 4];
+    let mut idx = 0;
+    while idx < 4 * 32 {
+        keys[idx / 32][idx % 32] = splitmix(idx as u64);
+        idx += 1;
+    }
+    keys
+};
+
+const BLACK_TO_MOVE: u64 = splitmix(4 * 32);
+
+/// Zobrist key of a position: the pieces on the board and the side to move.
+/// Equal positions always have equal keys, and different ones almost never do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZobristKey(u64);
+
+impl ZobristKey {
+    pub fn new(board: &Board, to_move: Player) -> Self {
+        let bits = BitBoard::from(board);
+        let mut key = ZobristKey(0);
+        for position in BitBoard::positions(bits.occupied()) {
+            let piece = bits.piece_at(position).expect("occupied square");
+            key.toggle(piece, position);
+        }
+        if to_move == Player::Black {
+            key.0 ^= BLACK_TO_MOVE;
+        }
+        key
+    }
+
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Adds the piece to the square, or takes it away when it is already there
+    fn toggle(&mut self, piece: Piece, position: Position) {
+        let idx = BitBoard::square_index(position).expect("pieces only stand on dark squares");
+        self.0 ^= PIECE_KEYS[piece as usize][idx as usize];
+    }
+
+    /// Updates the key after the move was made, passing the turn to the other
+    /// side
+    pub fn make(&mut self, delta: &MoveDelta) {
+        let enemy = delta.piece.player_affiliation().next_player();
+        self.toggle(delta.piece, delta.from);
+        self.toggle(delta.became, delta.to);
+        for position in BitBoard::positions(delta.captured & !delta.captured_queens) {
+            self.toggle(Piece::new(enemy, false), position);
+        }
+        for position in BitBoard::positions(delta.captured_queens) {
+            self.toggle(Piece::new(enemy, true), position);
+        }
+        self.0 ^= BLACK_TO_MOVE;
+    }
+
+    /// Takes back a move previously applied with `make`
+    pub fn unmake(&mut self, delta: &MoveDelta) {
+        // Toggling the same squares again restores every one of them
+        self.make(delta)
+    }
+}
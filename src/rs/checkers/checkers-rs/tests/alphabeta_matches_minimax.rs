@@ -0,0 +1,32 @@
+mod common;
+
+use checkers_rs::{alphabeta, minimax, Solution};
+use common::{play, rules};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+const MAX_DEPTH: u8 = 5;
+
+fn score(solution: &Solution) -> Option<i32> {
+    match solution {
+        Solution::NoMoves => None,
+        Solution::Score(score) | Solution::Move(_, score) => Some(*score),
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(48))]
+
+    #[test]
+    fn alphabeta_scores_like_minimax(
+        rules in rules(),
+        choices in prop::collection::vec(any::<Index>(), 0..60),
+    ) {
+        let (board, player) = play(&rules, &choices);
+        for depth in 1..=MAX_DEPTH {
+            let expected = minimax(&board, player, depth, &rules);
+            let actual = alphabeta(&board, player, depth, &rules);
+            prop_assert_eq!(score(&actual), score(&expected), "depth {}", depth);
+        }
+    }
+}
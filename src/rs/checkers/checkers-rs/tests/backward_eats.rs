@@ -0,0 +1,73 @@
+mod common;
+
+use checkers_rs::{can_eat, BitBoard, Diagonal, Player, Rules};
+use common::{moves, position};
+
+fn backwards() -> Rules {
+    Rules {
+        men_capture_backwards: true,
+        ..Rules::default()
+    }
+}
+
+#[test]
+fn man_eats_backward_when_the_rules_allow() {
+    let fen = "W:W18:B22";
+
+    assert_eq!(moves(fen, &backwards()), vec![(18, vec![25])]);
+    assert_eq!(
+        moves(fen, &Rules::default()),
+        vec![(18, vec![14]), (18, vec![15])]
+    );
+}
+
+#[test]
+fn black_man_eats_backward_too() {
+    assert_eq!(moves("B:W14:B18", &backwards()), vec![(18, vec![9])]);
+    assert_eq!(
+        moves("B:W14:B18", &Rules::default()),
+        vec![(18, vec![22]), (18, vec![23])]
+    );
+}
+
+#[test]
+fn man_eats_backward_and_forward_in_one_chain() {
+    let fen = "W:W14:B18,19";
+
+    assert_eq!(moves(fen, &backwards()), vec![(14, vec![23, 16])]);
+    assert_eq!(
+        moves(fen, &Rules::default()),
+        vec![(14, vec![9]), (14, vec![10])]
+    );
+}
+
+#[test]
+fn man_eats_either_way() {
+    assert_eq!(
+        moves("W:W18:B23,14", &backwards()),
+        vec![(18, vec![9]), (18, vec![27])]
+    );
+}
+
+#[test]
+fn man_still_moves_only_forward() {
+    assert_eq!(
+        moves("W:W18:B1", &backwards()),
+        vec![(18, vec![14]), (18, vec![15])]
+    );
+}
+
+#[test]
+fn backward_jumpers_are_found() {
+    let (board, _) = position("W:W18:B22");
+    let bits = BitBoard::from(&board);
+    let men = bits.pieces(Player::White);
+
+    assert_ne!(
+        bits.jumpers_along(Player::White, men, Diagonal::DownLeft),
+        0
+    );
+    assert_eq!(bits.jumpers(Player::White), 0);
+    assert_eq!(can_eat(&board, Player::White, &backwards()).count(), 1);
+    assert_eq!(can_eat(&board, Player::White, &Rules::default()).count(), 0);
+}
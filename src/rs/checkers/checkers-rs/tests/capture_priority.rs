@@ -0,0 +1,72 @@
+mod common;
+
+use checkers_rs::{CapturePriority, Rules};
+
+/// Eats of the side to move under the priority, as the square they start from
+/// and the squares they land on
+fn eats(fen: &str, capture_priority: CapturePriority) -> Vec<(u8, Vec<u8>)> {
+    common::moves(
+        fen,
+        &Rules {
+            capture_priority,
+            ..Rules::default()
+        },
+    )
+}
+
+#[test]
+fn any_eat_may_be_made_by_default() {
+    let fen = "W:W27,24:B23,14,19";
+
+    assert_eq!(
+        eats(fen, CapturePriority::Any),
+        vec![(24, vec![15]), (27, vec![18, 9])]
+    );
+}
+
+#[test]
+fn eat_of_the_most_pieces_is_made() {
+    let fen = "W:W27,24:B23,14,19";
+
+    assert_eq!(
+        eats(fen, CapturePriority::MostPieces),
+        vec![(27, vec![18, 9])]
+    );
+    assert_eq!(eats(fen, CapturePriority::Italian), vec![(27, vec![18, 9])]);
+}
+
+#[test]
+fn queen_eats_first_in_italian_draughts() {
+    let fen = "W:W27,K29:B23,14,25,K17";
+
+    assert_eq!(
+        eats(fen, CapturePriority::MostPieces),
+        vec![(27, vec![18, 9]), (29, vec![22, 13])]
+    );
+    assert_eq!(
+        eats(fen, CapturePriority::Italian),
+        vec![(29, vec![22, 13])]
+    );
+}
+
+#[test]
+fn eat_of_the_most_queens_is_made_in_italian_draughts() {
+    // Men eating two pieces, where two of the eats take the queen on 15
+    let men = "W:W27,28:B23,14,24,K15";
+    // Queens eating two pieces, where one takes the queen on 17
+    let queens = "W:WK27,K29:B23,14,25,K17";
+
+    assert_eq!(
+        eats(men, CapturePriority::MostPieces),
+        vec![(27, vec![18, 9]), (27, vec![18, 11]), (28, vec![19, 10])]
+    );
+    assert_eq!(
+        eats(men, CapturePriority::Italian),
+        vec![(27, vec![18, 11]), (28, vec![19, 10])]
+    );
+    assert_eq!(eats(queens, CapturePriority::MostPieces).len(), 2);
+    assert_eq!(
+        eats(queens, CapturePriority::Italian),
+        vec![(29, vec![22, 13])]
+    );
+}
@@ -0,0 +1,86 @@
+//! Positions shared by the tests. Not every test uses every helper.
+#![allow(dead_code)]
+
+use checkers_rs::{
+    available_moves, collect_available_moves, BitBoard, Board, CapturePriority,
+    MidCapturePromotion, Player, Position, Rules, Setup, Square, Variant,
+};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+/// Position of the dark square numbered from 1 to 32, with black starting on
+/// squares 1 to 12
+pub fn at(number: u8) -> Position {
+    Square::new(number).unwrap().into()
+}
+
+/// Number of the dark square at the position
+pub fn number(position: Position) -> u8 {
+    Square::try_from(position).unwrap().number()
+}
+
+pub fn numbers(positions: &[Position]) -> Vec<u8> {
+    positions.iter().copied().map(number).collect()
+}
+
+/// Numbers of the squares in the mask of a `BitBoard`
+pub fn squares(mask: u32) -> Vec<u8> {
+    BitBoard::positions(mask).map(number).collect()
+}
+
+/// Board and side to move written as in PDN, such as `W:W21-32,K5:B1-12`
+pub fn position(fen: &str) -> (Board, Player) {
+    let setup: Setup = fen.parse().unwrap();
+    (setup.board, setup.to_move)
+}
+
+pub fn rules() -> impl Strategy<Value = Rules> {
+    let priority = prop_oneof![
+        Just(CapturePriority::Any),
+        Just(CapturePriority::MostPieces),
+        Just(CapturePriority::Italian),
+    ];
+    let promotion = prop_oneof![
+        Just(MidCapturePromotion::EndMove),
+        Just(MidCapturePromotion::ContinueAsQueen),
+        Just(MidCapturePromotion::PassThrough),
+    ];
+    (any::<bool>(), any::<bool>(), priority, promotion).prop_map(
+        |(flying_kings, men_capture_backwards, capture_priority, mid_capture_promotion)| Rules {
+            flying_kings,
+            men_capture_backwards,
+            capture_priority,
+            mid_capture_promotion,
+            ..Rules::default()
+        },
+    )
+}
+
+/// Position reached by playing the chosen moves from the initial board,
+/// stopping early when the side to move has none
+pub fn play(rules: &Rules, choices: &[Index]) -> (Board, Player) {
+    let mut board = rules.initial_board();
+    let mut player = Player::White;
+    let mut moves = Vec::new();
+    for choice in choices {
+        moves.clear();
+        collect_available_moves(&board, player, rules, &mut moves);
+        if moves.is_empty() {
+            break;
+        }
+        board.make(choice.get(&moves));
+        player = player.next_player();
+    }
+    (board, player)
+}
+
+/// Moves of the side to move in the FEN position, as the square they start
+/// from and the squares they land on, in increasing order
+pub fn moves(fen: &str, rules: &Rules) -> Vec<(u8, Vec<u8>)> {
+    let (board, player) = position(fen);
+    let mut res: Vec<_> = available_moves(&board, player, rules)
+        .map(|mv| (number(mv.from), mv.path.into_iter().map(number).collect()))
+        .collect();
+    res.sort();
+    res
+}
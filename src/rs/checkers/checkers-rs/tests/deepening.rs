@@ -0,0 +1,140 @@
+use std::cell::Cell;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+use checkers_rs::{
+    iterative_deepening, Board, Clock, Player, Rules, SearchLimits, SearchResult, Stop,
+    TranspositionTable,
+};
+
+/// Clock that moves on by a second every time it is read
+#[derive(Default)]
+struct TickingClock {
+    reads: Cell<u64>,
+}
+
+impl Clock for TickingClock {
+    fn now(&self) -> Duration {
+        let reads = self.reads.get();
+        self.reads.set(reads + 1);
+        Duration::from_secs(reads)
+    }
+}
+
+fn search(limits: &SearchLimits) -> SearchResult {
+    search_until(limits, &|| false)
+}
+
+fn search_until(limits: &SearchLimits, stop: &dyn Stop) -> SearchResult {
+    let mut table = TranspositionTable::new(4);
+    let clock = TickingClock::default();
+    iterative_deepening(
+        &Board::default(),
+        Player::White,
+        &Rules::default(),
+        limits,
+        &clock,
+        stop,
+        &mut table,
+    )
+}
+
+#[test]
+fn zero_depth_still_searches_the_first_iteration() {
+    let result = search(&SearchLimits {
+        max_depth: 0,
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(result.depth, 1);
+    assert!(result.best_move().is_some());
+}
+
+#[test]
+fn deadline_already_passed_stops_after_the_first_iteration() {
+    let result = search(&SearchLimits {
+        time: Some(Duration::ZERO),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(result.depth, 1);
+    assert!(result.best_move().is_some());
+}
+
+#[test]
+fn deadline_stops_the_search() {
+    let time = Duration::from_secs(5);
+
+    let result = search(&SearchLimits {
+        time: Some(time),
+        ..SearchLimits::default()
+    });
+
+    assert!(result.depth >= 1);
+    assert!(result.elapsed >= time);
+    assert!(result.elapsed <= time + Duration::from_secs(2));
+    assert!(result.best_move().is_some());
+}
+
+#[test]
+fn node_limit_stops_the_search() {
+    let nodes = 5000;
+
+    let result = search(&SearchLimits {
+        nodes: Some(nodes),
+        ..SearchLimits::default()
+    });
+
+    assert!(result.depth > 1);
+    assert!(result.nodes <= nodes);
+    assert!(result.best_move().is_some());
+}
+
+#[test]
+fn node_limit_lets_the_first_iteration_complete() {
+    let result = search(&SearchLimits {
+        nodes: Some(1),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(result.depth, 1);
+    assert!(result.best_move().is_some());
+}
+
+#[test]
+fn stop_raised_before_the_search_lets_the_first_iteration_complete() {
+    let stop = AtomicBool::new(true);
+
+    let result = search_until(&SearchLimits::default(), &stop);
+
+    assert_eq!(result.depth, 1);
+    assert!(result.best_move().is_some());
+}
+
+#[test]
+fn stop_raised_mid_search_returns_the_last_completed_iteration() {
+    // Polled after every iteration and every 1024 nodes within one, so some
+    // of these stops come in the middle of an iteration
+    let mut stopped_mid_iteration = 0;
+    for raised_at in 1..=20 {
+        let polls = Cell::new(0);
+        let stop = || {
+            polls.set(polls.get() + 1);
+            polls.get() >= raised_at
+        };
+
+        let stopped = search_until(&SearchLimits::default(), &stop);
+        let completed = search(&SearchLimits {
+            max_depth: stopped.depth,
+            ..SearchLimits::default()
+        });
+
+        assert_eq!(stopped.solution(), completed.solution());
+        assert!(stopped.nodes >= completed.nodes);
+        // Nodes of the iteration that got stopped count too
+        if stopped.nodes > completed.nodes {
+            stopped_mid_iteration += 1;
+        }
+    }
+    assert!(stopped_mid_iteration > 0);
+}
@@ -0,0 +1,99 @@
+use checkers_rs::{Board, Cell, FenError, Orientation, Player, Setup, Square};
+
+fn setup(fen: &str) -> Setup {
+    fen.parse().unwrap()
+}
+
+fn cell(board: &Board, number: u8) -> Cell {
+    let square = Square::new(number).unwrap();
+    board.cell_at(square.position(Orientation::default()))
+}
+
+#[test]
+fn initial_position_is_read_from_ranges() {
+    let setup = setup("B:W21-32:B1-12");
+
+    assert_eq!(setup.board, Board::default());
+    assert_eq!(setup.to_move, Player::Black);
+}
+
+#[test]
+fn queens_and_single_squares_are_read() {
+    let setup = setup("W:W21,K30:BK1,5");
+
+    assert_eq!(setup.to_move, Player::White);
+    assert_eq!(cell(&setup.board, 21), Cell::WhitePiece);
+    assert_eq!(cell(&setup.board, 30), Cell::WhiteQueen);
+    assert_eq!(cell(&setup.board, 1), Cell::BlackQueen);
+    assert_eq!(cell(&setup.board, 5), Cell::BlackPiece);
+    assert_eq!(cell(&setup.board, 22).into_piece(), None);
+}
+
+#[test]
+fn piece_lists_may_come_in_either_order() {
+    assert_eq!(setup("W:B1,2:W31,32"), setup("W:W31,32:B1,2"));
+}
+
+#[test]
+fn trailing_period_and_spaces_are_ignored() {
+    assert_eq!(setup(" W:W 21, 22:B 1 .\n"), setup("W:W21,22:B1"));
+}
+
+#[test]
+fn side_without_pieces_has_an_empty_list() {
+    let setup = setup("B:W21:B");
+
+    assert_eq!(setup.to_string(), "B:W21:B");
+}
+
+#[test]
+fn setup_survives_a_round_trip() {
+    let fens = [
+        "B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12",
+        "W:W18,K30:BK3,14,15",
+        "B:WK4:B",
+    ];
+    for fen in fens {
+        let setup = setup(fen);
+
+        assert_eq!(setup.to_string(), fen);
+        assert_eq!(setup.to_string().parse::<Setup>(), Ok(setup));
+    }
+}
+
+#[test]
+fn board_survives_a_round_trip() {
+    let board = Board::default();
+
+    let setup = setup(&Setup::new(board, Player::White).to_string());
+
+    assert_eq!(setup, Setup::new(board, Player::White));
+}
+
+#[test]
+fn errors_are_reported() {
+    let cases = [
+        ("W:W21", FenError::Sections),
+        ("W:W21:B1:B2", FenError::Sections),
+        ("X:W21:B1", FenError::Player),
+        ("WB:W21:B1", FenError::Player),
+        ("W:X21:B1", FenError::Player),
+        ("W::B1", FenError::Player),
+        ("W:W1:W2", FenError::Lists),
+        ("W:B1:B2", FenError::Lists),
+        ("W:W33:B1", FenError::Square),
+        ("W:W0:B1", FenError::Square),
+        ("W:Wa:B1", FenError::Square),
+        ("W:W21-:B1", FenError::Square),
+        ("B:B12-1:W21", FenError::Range),
+        ("W:W21:B21", FenError::Occupied(Square::new(21).unwrap())),
+        ("W:W21,21:B1", FenError::Occupied(Square::new(21).unwrap())),
+        (
+            "W:W20-22:B1,K21",
+            FenError::Occupied(Square::new(21).unwrap()),
+        ),
+    ];
+    for (fen, err) in cases {
+        assert_eq!(fen.parse::<Setup>(), Err(err), "{}", fen);
+    }
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use checkers_rs::{BitBoard, Player, Rules};
+use common::{moves, position};
+
+fn flying() -> Rules {
+    Rules {
+        flying_kings: true,
+        ..Rules::default()
+    }
+}
+
+fn landings(squares: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    squares.iter().map(|&square| (29, vec![square])).collect()
+}
+
+#[test]
+fn queen_flies_along_the_whole_diagonal() {
+    let fen = "W:WK29:B";
+
+    assert_eq!(moves(fen, &flying()), landings(&[4, 8, 11, 15, 18, 22, 25]));
+    assert_eq!(moves(fen, &Rules::default()), landings(&[25]));
+}
+
+#[test]
+fn queen_eats_from_afar_and_lands_anywhere_behind() {
+    let fen = "W:WK29:B15";
+
+    assert_eq!(moves(fen, &flying()), landings(&[4, 8, 11]));
+    // Without flying kings the man is out of reach
+    assert_eq!(moves(fen, &Rules::default()), landings(&[25]));
+}
+
+#[test]
+fn queen_does_not_jump_two_pieces_in_a_row() {
+    assert_eq!(moves("W:WK29:B15,11", &flying()), landings(&[18, 22, 25]));
+}
+
+#[test]
+fn queen_does_not_fly_over_its_own_pieces() {
+    assert_eq!(
+        moves("W:WK29,22:B15", &flying()),
+        vec![(22, vec![17]), (22, vec![18]), (29, vec![25])]
+    );
+}
+
+#[test]
+fn queen_turns_to_eat_again() {
+    let chains: Vec<_> = moves("W:WK29:B18,10", &flying())
+        .into_iter()
+        .filter(|(_, path)| path.len() == 2)
+        .collect();
+
+    assert_eq!(chains, vec![(29, vec![15, 1]), (29, vec![15, 6])]);
+}
+
+#[test]
+fn flying_jumpers_see_enemies_at_any_distance() {
+    let jumpers = |fen: &str| BitBoard::from(&position(fen).0).flying_jumpers(Player::White);
+
+    assert_ne!(jumpers("W:WK29:B15"), 0);
+    assert_ne!(jumpers("W:WK29:B25"), 0);
+    assert_eq!(jumpers("W:WK29:B15,11"), 0);
+    assert_eq!(jumpers("W:WK29,22:B15"), 0);
+    assert_eq!(jumpers("W:WK29:B4"), 0);
+}
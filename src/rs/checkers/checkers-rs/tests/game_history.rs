@@ -0,0 +1,138 @@
+use checkers_rs::{
+    available_moves, Board, DrawReason, Game, GameOutcome, IllegalMove, Player, Rules, ZobristKey,
+};
+
+fn game() -> Game {
+    Game::new(Rules::default())
+}
+
+/// Plays the first available move
+fn play_first(game: &mut Game) {
+    let mv = game.available_moves().remove(0);
+    game.play(&mv).unwrap();
+}
+
+fn assert_key_matches(game: &Game) {
+    assert_eq!(game.key(), ZobristKey::new(game.board(), game.to_move()));
+}
+
+#[test]
+fn new_game_has_nothing_to_undo_or_redo() {
+    let mut game = game();
+
+    assert!(!game.can_undo());
+    assert!(!game.can_redo());
+    assert_eq!(game.undo(), None);
+    assert_eq!(game.redo(), None);
+    assert_eq!(game.to_move(), Player::White);
+}
+
+#[test]
+fn undo_takes_back_the_last_move() {
+    let mut game = game();
+    play_first(&mut game);
+    let after_first = *game.board();
+    play_first(&mut game);
+
+    let undone = game.undo().unwrap();
+
+    assert_eq!(*game.board(), after_first);
+    assert_eq!(game.to_move(), Player::Black);
+    assert_eq!(game.history().len(), 1);
+    assert!(game.can_redo());
+    assert_eq!(undone.piece.player_affiliation(), Player::Black);
+    assert_key_matches(&game);
+}
+
+#[test]
+fn redo_plays_the_undone_move_again() {
+    let mut game = game();
+    play_first(&mut game);
+    play_first(&mut game);
+    let after_second = *game.board();
+    let key = game.key();
+
+    game.undo();
+    game.undo();
+    assert_eq!(*game.board(), Board::default());
+    game.redo();
+    game.redo();
+
+    assert_eq!(*game.board(), after_second);
+    assert_eq!(game.key(), key);
+    assert_eq!(game.to_move(), Player::White);
+    assert!(!game.can_redo());
+}
+
+#[test]
+fn playing_after_undo_drops_the_undone_moves() {
+    let mut game = game();
+    play_first(&mut game);
+    play_first(&mut game);
+    game.undo();
+
+    let other = game.available_moves().pop().unwrap();
+    game.play(&other).unwrap();
+
+    assert!(!game.can_redo());
+    assert_eq!(game.history().len(), 2);
+    assert_eq!(game.played_moves()[1], other);
+    assert_key_matches(&game);
+}
+
+#[test]
+fn rewind_keeps_every_move_for_redo() {
+    let mut game = game();
+    for _ in 0..4 {
+        play_first(&mut game);
+    }
+    let board = *game.board();
+
+    game.rewind();
+    assert_eq!(*game.board(), *game.start());
+    assert!(!game.can_undo());
+    while game.redo().is_some() {}
+
+    assert_eq!(*game.board(), board);
+    assert_key_matches(&game);
+}
+
+#[test]
+fn move_of_the_other_side_is_not_played() {
+    let mut game = game();
+    let black = available_moves(&Board::default(), Player::Black, &Rules::default())
+        .next()
+        .unwrap();
+
+    assert_eq!(game.play(&black), Err(IllegalMove::NotYourPiece));
+    assert!(!game.can_undo());
+    assert_eq!(*game.board(), Board::default());
+}
+
+#[test]
+fn replay_goes_through_every_board_of_the_game() {
+    let mut game = game();
+    for _ in 0..3 {
+        play_first(&mut game);
+    }
+    let played = game.played_moves();
+
+    let boards: Vec<_> = game.replay().map(|(board, _)| board).collect();
+
+    assert_eq!(boards[0], Board::default());
+    assert_eq!(boards[1], played[0].next_board);
+    assert_eq!(boards[2], played[1].next_board);
+    assert_eq!(played[2].next_board, *game.board());
+}
+
+#[test]
+fn undo_takes_back_a_draw_agreement() {
+    let mut game = game();
+    play_first(&mut game);
+    game.agree_draw();
+    assert_eq!(game.outcome(), GameOutcome::Draw(DrawReason::Agreement));
+
+    game.undo();
+
+    assert_eq!(game.outcome(), GameOutcome::Ongoing);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use checkers_rs::{legal_move, Board, Cell, IllegalMove, Player, Rules};
+use common::{at, position};
+
+/// White man on 27 that has to eat 23 and then 14, while 30 cannot eat
+const CHAIN: &str = "W:W27,30:B23,14";
+
+/// Why the move of the side to move along the squares is illegal
+fn error(fen: &str, squares: &[u8]) -> IllegalMove {
+    let (board, to_move) = position(fen);
+    let path: Vec<_> = squares[1..].iter().map(|&number| at(number)).collect();
+    legal_move(&board, to_move, at(squares[0]), &path, &Rules::default()).unwrap_err()
+}
+
+fn start(squares: &[u8]) -> IllegalMove {
+    error("W:W21-32:B1-12", squares)
+}
+
+#[test]
+fn legal_move_is_applied() {
+    let board = Board::default();
+
+    let next = board
+        .try_apply(Player::White, at(22), &[at(18)], &Rules::default())
+        .unwrap();
+
+    assert_eq!(next.cell_at(at(18)), Cell::WhitePiece);
+    assert!(next.cell_at(at(22)).into_piece().is_none());
+}
+
+#[test]
+fn move_that_lands_nowhere_is_illegal() {
+    assert_eq!(start(&[22]), IllegalMove::EmptyPath);
+}
+
+#[test]
+fn move_from_an_empty_square_is_illegal() {
+    assert_eq!(start(&[18, 14]), IllegalMove::NoPiece);
+}
+
+#[test]
+fn piece_of_the_other_player_is_not_moved() {
+    assert_eq!(start(&[11, 15]), IllegalMove::NotYourPiece);
+}
+
+#[test]
+fn hop_off_the_diagonals_is_illegal() {
+    assert_eq!(start(&[22, 14]), IllegalMove::NotDiagonal);
+}
+
+#[test]
+fn move_onto_a_piece_is_illegal() {
+    assert_eq!(start(&[26, 22]), IllegalMove::DestinationOccupied);
+}
+
+#[test]
+fn man_does_not_go_backward() {
+    assert_eq!(
+        error("W:W18:B1", &[18, 22]),
+        IllegalMove::MenCannotMoveBackward
+    );
+    assert_eq!(
+        error("W:W18:B22", &[18, 25]),
+        IllegalMove::MenCannotMoveBackward
+    );
+}
+
+#[test]
+fn simple_move_is_illegal_while_the_player_can_eat() {
+    assert_eq!(error(CHAIN, &[30, 26]), IllegalMove::CaptureIsMandatory);
+}
+
+#[test]
+fn eat_that_stops_short_is_illegal() {
+    assert_eq!(error(CHAIN, &[27, 18]), IllegalMove::IncompleteCapture);
+}
+
+#[test]
+fn move_the_rules_do_not_allow_is_illegal() {
+    // A man steps over an empty square
+    assert_eq!(start(&[22, 15]), IllegalMove::NotAvailable);
+    // The second hop of the eat does not jump over anything
+    assert_eq!(error(CHAIN, &[27, 18, 11]), IllegalMove::NotAvailable);
+}
+
+#[test]
+fn try_apply_reports_the_illegal_move() {
+    let board = Board::default();
+
+    let err = board
+        .try_apply(Player::Black, at(22), &[at(18)], &Rules::default())
+        .unwrap_err();
+
+    assert_eq!(err, IllegalMove::NotYourPiece);
+}
@@ -0,0 +1,91 @@
+use checkers_rs::{
+    available_moves, c, Board, Cell, MidCapturePromotion, Move, Player, Position, Rules,
+};
+
+/// White man about to eat onto the promotion row at (3, 0), with another black
+/// piece it can eat from there only by going backwards
+fn board() -> Board {
+    let mut board = Board::empty();
+    board.replace(Position::new(c!(5), c!(2)), Cell::WhitePiece);
+    board.replace(Position::new(c!(4), c!(1)), Cell::BlackPiece);
+    board.replace(Position::new(c!(2), c!(1)), Cell::BlackPiece);
+    board
+}
+
+fn rules(mid_capture_promotion: MidCapturePromotion) -> Rules {
+    Rules {
+        flying_kings: true,
+        men_capture_backwards: true,
+        mid_capture_promotion,
+        ..Rules::default()
+    }
+}
+
+fn moves(board: &Board, rules: &Rules) -> Vec<Move> {
+    available_moves(board, Player::White, rules).collect()
+}
+
+fn landed(mv: &Move) -> Cell {
+    mv.next_board.cell_at(mv.to)
+}
+
+#[test]
+fn end_move_stops_on_promotion() {
+    let moves = moves(&board(), &rules(MidCapturePromotion::EndMove));
+
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].path, vec![Position::new(c!(3), c!(0))]);
+    assert_eq!(moves[0].captured, vec![Position::new(c!(4), c!(1))]);
+    assert_eq!(landed(&moves[0]), Cell::WhiteQueen);
+}
+
+#[test]
+fn end_move_is_the_default() {
+    let default = Rules {
+        flying_kings: true,
+        men_capture_backwards: true,
+        ..Rules::default()
+    };
+    let expected = moves(&board(), &rules(MidCapturePromotion::EndMove));
+
+    assert_eq!(moves(&board(), &default), expected);
+}
+
+#[test]
+fn continue_as_queen_eats_on_like_a_queen() {
+    let moves = moves(&board(), &rules(MidCapturePromotion::ContinueAsQueen));
+
+    // A flying queen may land on any empty square behind the eaten piece
+    let ends: Vec<Position> = moves.iter().map(|mv| mv.to).collect();
+    assert_eq!(
+        ends,
+        vec![Position::new(c!(1), c!(2)), Position::new(c!(0), c!(3))]
+    );
+    for mv in &moves {
+        assert_eq!(mv.captured.len(), 2);
+        assert_eq!(landed(mv), Cell::WhiteQueen);
+    }
+}
+
+#[test]
+fn pass_through_eats_on_like_a_man() {
+    let moves = moves(&board(), &rules(MidCapturePromotion::PassThrough));
+
+    assert_eq!(moves.len(), 1);
+    assert_eq!(
+        moves[0].path,
+        vec![Position::new(c!(3), c!(0)), Position::new(c!(1), c!(2))]
+    );
+    assert_eq!(moves[0].captured.len(), 2);
+    assert_eq!(landed(&moves[0]), Cell::WhitePiece);
+}
+
+#[test]
+fn pass_through_promotes_when_the_move_ends_on_the_row() {
+    let mut board = board();
+    board.remove(Position::new(c!(2), c!(1)));
+    let moves = moves(&board, &rules(MidCapturePromotion::PassThrough));
+
+    assert_eq!(moves.len(), 1);
+    assert_eq!(landed(&moves[0]), Cell::WhiteQueen);
+}
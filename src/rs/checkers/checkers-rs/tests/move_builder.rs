@@ -0,0 +1,96 @@
+mod common;
+
+use checkers_rs::{Board, Cell, HopError, MoveBuilder, Player, Rules};
+use common::{at, numbers, position};
+
+/// White man on 27 that eats 23 and then either 14 or 15
+const BRANCHING: &str = "W:W27,30:B23,14,15";
+
+fn builder(fen: &str, from: u8) -> Option<MoveBuilder> {
+    let (board, player) = position(fen);
+    MoveBuilder::new(&board, player, at(from), &Rules::default())
+}
+
+/// Squares the next hop may land on, in increasing order
+fn next_hops(builder: &MoveBuilder) -> Vec<u8> {
+    let mut res = numbers(&builder.next_hops());
+    res.sort_unstable();
+    res
+}
+
+#[test]
+fn piece_of_the_opponent_is_not_moved() {
+    let board = Board::default();
+
+    assert!(MoveBuilder::new(&board, Player::White, at(11), &Rules::default()).is_none());
+    assert!(MoveBuilder::new(&board, Player::Black, at(11), &Rules::default()).is_some());
+    assert!(MoveBuilder::new(&board, Player::White, at(18), &Rules::default()).is_none());
+}
+
+#[test]
+fn multi_jump_is_entered_hop_by_hop() {
+    let mut builder = builder("W:W27:B23,14", 27).unwrap();
+    assert_eq!(next_hops(&builder), vec![18]);
+
+    builder.push(at(18)).unwrap();
+    assert!(!builder.is_complete());
+    assert!(builder.finish().is_none());
+    assert_eq!(next_hops(&builder), vec![9]);
+    let board = builder.current_board();
+    assert!(board.cell_at(at(27)).into_piece().is_none());
+    assert!(board.cell_at(at(23)).into_piece().is_none());
+    assert_eq!(board.cell_at(at(18)), Cell::WhitePiece);
+    assert_eq!(board.cell_at(at(14)), Cell::BlackPiece);
+
+    builder.push(at(9)).unwrap();
+    let mv = builder.finish().unwrap();
+    assert_eq!(mv.path, vec![at(18), at(9)]);
+    assert_eq!(numbers(&mv.captured), vec![23, 14]);
+    assert_eq!(builder.current_board(), mv.next_board);
+    assert_eq!(builder.push(at(5)), Err(HopError::Complete));
+}
+
+#[test]
+fn hop_off_every_legal_move_is_rejected() {
+    let mut builder = builder("W:W27:B23,14", 27).unwrap();
+
+    assert_eq!(builder.push(at(24)), Err(HopError::IllegalHop(at(24))));
+    assert_eq!(builder.push(at(9)), Err(HopError::IllegalHop(at(9))));
+    assert!(builder.path().is_empty());
+}
+
+#[test]
+fn branching_chain_goes_either_way() {
+    let mut builder = builder(BRANCHING, 27).unwrap();
+    builder.push(at(18)).unwrap();
+    assert_eq!(next_hops(&builder), vec![9, 11]);
+
+    builder.push(at(9)).unwrap();
+    let left = builder.finish().unwrap();
+    assert_eq!(builder.pop(), Some(at(9)));
+    builder.push(at(11)).unwrap();
+    let right = builder.finish().unwrap();
+
+    assert_eq!(numbers(&left.captured), vec![23, 14]);
+    assert_eq!(numbers(&right.captured), vec![23, 15]);
+    assert_eq!(builder.path(), &[at(18), at(11)]);
+}
+
+#[test]
+fn chains_that_part_at_the_first_hop_end_on_the_same_square() {
+    let mut builder = builder("W:W27:B23,15,24,16", 27).unwrap();
+    assert_eq!(next_hops(&builder), vec![18, 20]);
+
+    builder.push(at(20)).unwrap();
+    builder.push(at(11)).unwrap();
+
+    assert_eq!(numbers(&builder.finish().unwrap().captured), vec![24, 16]);
+}
+
+#[test]
+fn piece_that_cannot_eat_cannot_move_while_another_can() {
+    let builder = builder(BRANCHING, 30).unwrap();
+
+    assert!(!builder.can_move());
+    assert!(builder.next_hops().is_empty());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use checkers_rs::{
+    available_moves, can_eat, collect_available_moves, has_moves, BitBoard, CapturePriority,
+    Player, Rules,
+};
+use common::{moves, number, numbers, play, position, rules, squares};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+fn bits(fen: &str) -> BitBoard {
+    BitBoard::from(&position(fen).0)
+}
+
+#[test]
+fn front_men_move_at_the_start() {
+    let rules = Rules::default();
+
+    assert_eq!(
+        moves("W:W21-32:B1-12", &rules),
+        vec![
+            (21, vec![17]),
+            (22, vec![17]),
+            (22, vec![18]),
+            (23, vec![18]),
+            (23, vec![19]),
+            (24, vec![19]),
+            (24, vec![20]),
+        ]
+    );
+    assert_eq!(
+        moves("B:W21-32:B1-12", &rules),
+        vec![
+            (9, vec![13]),
+            (9, vec![14]),
+            (10, vec![14]),
+            (10, vec![15]),
+            (11, vec![15]),
+            (11, vec![16]),
+            (12, vec![16]),
+        ]
+    );
+    assert_eq!(
+        squares(bits("W:W21-32:B1-12").movers(Player::White)),
+        vec![21, 22, 23, 24]
+    );
+    assert_eq!(
+        squares(bits("W:W21-32:B1-12").movers(Player::Black)),
+        vec![9, 10, 11, 12]
+    );
+}
+
+#[test]
+fn queen_moves_both_ways_and_man_only_forward() {
+    let rules = Rules::default();
+
+    assert_eq!(
+        moves("W:WK18:B1", &rules),
+        vec![
+            (18, vec![14]),
+            (18, vec![15]),
+            (18, vec![22]),
+            (18, vec![23])
+        ]
+    );
+    assert_eq!(
+        moves("W:W18:B1", &rules),
+        vec![(18, vec![14]), (18, vec![15])]
+    );
+}
+
+#[test]
+fn only_eats_are_made_when_a_piece_can_eat() {
+    let fen = "W:W18,30:B23,14";
+    let rules = Rules::default();
+
+    assert_eq!(moves(fen, &rules), vec![(18, vec![9])]);
+    assert_eq!(squares(bits(fen).jumpers(Player::White)), vec![18]);
+    assert_eq!(squares(bits(fen).movers(Player::White)), vec![18, 30]);
+}
+
+#[test]
+fn multi_jump_reports_every_landing_and_eaten_piece() {
+    let (board, player) = position("W:W27:B24,16");
+    let moves: Vec<_> = available_moves(&board, player, &Rules::default()).collect();
+
+    assert_eq!(moves.len(), 1);
+    assert_eq!(number(moves[0].to), 11);
+    assert_eq!(numbers(&moves[0].path), vec![20, 11]);
+    assert_eq!(numbers(&moves[0].captured), vec![24, 16]);
+}
+
+#[test]
+fn pieces_surrounded_at_the_edge_cannot_move() {
+    let fen = "W:W29,30,31,32:B21-28";
+    let rules = Rules::default();
+
+    assert!(moves(fen, &rules).is_empty());
+    assert!(!bits(fen).has_moves(Player::White));
+    assert!(!bits(fen).has_moves(Player::Black));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    /// The quick checks on the masks of pieces agree with the moves the
+    /// generator finds
+    #[test]
+    fn has_moves_and_can_eat_agree_with_the_moves(
+        rules in rules(),
+        choices in prop::collection::vec(any::<Index>(), 0..120),
+    ) {
+        let (board, _) = play(&rules, &choices);
+        // Eats of every priority, so every piece that can eat makes one
+        let any_eat = Rules { capture_priority: CapturePriority::Any, ..rules };
+        for player in [Player::White, Player::Black] {
+            let mut moves = Vec::new();
+            collect_available_moves(&board, player, &any_eat, &mut moves);
+            let mut eaters: Vec<_> = moves
+                .iter()
+                .filter(|mv| mv.is_eat())
+                .map(|mv| number(mv.from))
+                .collect();
+            eaters.dedup();
+            let jumpers: Vec<_> = can_eat(&board, player, &rules).map(number).collect();
+
+            prop_assert_eq!(has_moves(&board, player, &rules), !moves.is_empty());
+            prop_assert_eq!(jumpers, eaters);
+        }
+    }
+}
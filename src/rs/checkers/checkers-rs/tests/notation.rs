@@ -0,0 +1,83 @@
+mod common;
+
+use checkers_rs::{format_move, parse_move, Move, NotationError, Rules, Square};
+use common::{numbers, position};
+
+/// Initial position, white to move
+const START: &str = "W:W21-32:B1-12";
+
+/// White man on 27 that can eat to 11 through either 18 or 20
+const TWO_WAYS: &str = "W:W27:B23,15,24,16";
+
+fn parse(fen: &str, text: &str) -> Result<Move, NotationError> {
+    let (board, to_move) = position(fen);
+    parse_move(&board, to_move, text, &Rules::default())
+}
+
+fn format(fen: &str, mv: &Move) -> String {
+    format_move(&position(fen).0, mv, &Rules::default())
+}
+
+#[test]
+fn simple_move_is_written_and_read() {
+    let mv = parse(START, "22-18").unwrap();
+
+    assert_eq!(numbers(&[mv.from]), vec![22]);
+    assert_eq!(numbers(&mv.path), vec![18]);
+    assert_eq!(format(START, &mv), "22-18");
+}
+
+#[test]
+fn multi_jump_is_written_by_its_ends() {
+    let fen = "W:W27:B23,14";
+
+    let mv = parse(fen, "27x9").unwrap();
+
+    assert_eq!(numbers(&mv.path), vec![18, 9]);
+    assert_eq!(numbers(&mv.captured), vec![23, 14]);
+    assert_eq!(format(fen, &mv), "27x9");
+    assert_eq!(parse(fen, "27x18x9"), Ok(mv.clone()));
+    assert_eq!(parse(fen, "27-9"), Ok(mv));
+}
+
+#[test]
+fn ambiguous_capture_is_written_with_every_landing() {
+    let left = parse(TWO_WAYS, "27x18x11").unwrap();
+    let right = parse(TWO_WAYS, "27x20x11").unwrap();
+
+    assert_eq!(numbers(&left.captured), vec![23, 15]);
+    assert_eq!(numbers(&right.captured), vec![24, 16]);
+    assert_eq!(format(TWO_WAYS, &left), "27x18x11");
+    assert_eq!(format(TWO_WAYS, &right), "27x20x11");
+    assert_eq!(parse(TWO_WAYS, "27x11"), Err(NotationError::Ambiguous));
+}
+
+#[test]
+fn text_that_is_not_squares_is_rejected() {
+    for text in ["", "22", "22-", "a-b", "22/18", "22-33", "0-4"] {
+        assert_eq!(parse(START, text), Err(NotationError::Syntax), "{}", text);
+    }
+}
+
+#[test]
+fn move_from_an_empty_square_is_rejected() {
+    let err = parse(TWO_WAYS, "26-22").unwrap_err();
+
+    assert_eq!(err, NotationError::NoPiece(Square::new(26).unwrap()));
+}
+
+#[test]
+fn move_of_the_side_not_to_move_is_illegal() {
+    let black_to_move = "B:W21-32:B1-12";
+
+    assert_eq!(parse(START, "11-15"), Err(NotationError::Illegal));
+    assert!(parse(black_to_move, "11-15").is_ok());
+    assert_eq!(parse(black_to_move, "22-18"), Err(NotationError::Illegal));
+}
+
+#[test]
+fn move_no_piece_can_make_is_illegal() {
+    assert_eq!(parse(START, "22-17-13"), Err(NotationError::Illegal));
+    assert_eq!(parse(START, "22-15"), Err(NotationError::Illegal));
+    assert_eq!(parse(TWO_WAYS, "27-23"), Err(NotationError::Illegal));
+}
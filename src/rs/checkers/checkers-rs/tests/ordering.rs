@@ -0,0 +1,101 @@
+use std::time::Instant;
+
+use checkers_rs::game::ordering::MoveOrder;
+use checkers_rs::{
+    collect_available_moves, iterative_deepening, MoveDelta, Orientation, Rules, SearchLimits,
+    Setup, Square, TranspositionTable, Variant,
+};
+
+const DEPTH: u8 = 8;
+
+/// Openings and endgames with eats and queens around
+const POSITIONS: [&str; 5] = [
+    "W:W21-32:B1-12",
+    "W:W18,21,22,23,25-32:B1-11,16",
+    "B:W17,21,24-28,30,31:B1,2,3,5,6,7,9,10,12",
+    "W:WK14,22,26,27:B5,7,K19,11,12",
+    "W:W31,27,26,K10:B1,K29,18,20",
+];
+
+/// English draughts where a player who can eat may make a simple move instead,
+/// so eats and simple moves get ordered together
+struct OptionalCaptures;
+
+impl Variant for OptionalCaptures {
+    fn capture_is_mandatory(&self) -> bool {
+        false
+    }
+}
+
+fn squares(mv: &MoveDelta) -> (u8, u8) {
+    let number = |position| {
+        Square::from_position(position, Orientation::default())
+            .unwrap()
+            .number()
+    };
+    (number(mv.from), number(mv.to))
+}
+
+#[test]
+fn ordering_keeps_the_score_and_saves_nodes() {
+    for fen in POSITIONS {
+        let setup: Setup = fen.parse().unwrap();
+        let search = |move_ordering| {
+            let limits = SearchLimits {
+                max_depth: DEPTH,
+                move_ordering,
+                ..SearchLimits::default()
+            };
+            let mut table = TranspositionTable::new(4);
+            let clock = Instant::now();
+            iterative_deepening(
+                &setup.board,
+                setup.to_move,
+                &Rules::default(),
+                &limits,
+                &clock,
+                &|| false,
+                &mut table,
+            )
+        };
+
+        let ordered = search(true);
+        let unordered = search(false);
+
+        assert_eq!(ordered.score, unordered.score, "{}", fen);
+        assert_eq!(ordered.depth, unordered.depth, "{}", fen);
+        assert!(ordered.nodes <= unordered.nodes, "{}", fen);
+    }
+}
+
+#[test]
+fn moves_are_ranked_by_hash_move_eats_killers_and_history() {
+    let setup: Setup = "W:W24,27,30,31,32:B14,19,23".parse().unwrap();
+    let mut moves = Vec::new();
+    collect_available_moves(&setup.board, setup.to_move, &OptionalCaptures, &mut moves);
+    let find = |from, to| *moves.iter().find(|mv| squares(mv) == (from, to)).unwrap();
+    let hash_move = find(31, 26);
+    let mut order = MoveOrder::default();
+    // Killers of the ply, the last one first
+    order.cutoff(&find(30, 25), 0, 1);
+    order.cutoff(&find(30, 26), 0, 1);
+    // Cutoffs at other plies only count for the history
+    order.cutoff(&find(24, 20), 1, 2);
+    order.cutoff(&find(32, 28), 2, 4);
+
+    order.sort(&mut moves, Some(hash_move), 0);
+
+    let sorted: Vec<_> = moves.iter().map(squares).collect();
+    assert_eq!(
+        sorted,
+        vec![
+            (31, 26),
+            (27, 9),
+            (24, 15),
+            (30, 26),
+            (30, 25),
+            (32, 28),
+            (24, 20),
+        ]
+    );
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use checkers_rs::game::evaluate::{evaluate, WIN_SCORE};
+use checkers_rs::{DrawReason, Game, GameOutcome, Player, Rules, WinReason};
+use common::{at, position};
+
+/// Men of both sides stuck against each other at white's end of the board, so
+/// whoever is to move has no moves
+const BLOCKED: &str = "W:W29,30,31,32:B21-28";
+
+/// Lone queens in opposite corners
+const QUEENS: &str = "W:WK29:BK4";
+
+fn game(rules: Rules, fen: &str) -> Game {
+    let (board, to_move) = position(fen);
+    Game::from_position(rules, board, to_move)
+}
+
+/// Plays the move between the squares
+fn play(game: &mut Game, from: u8, to: u8) {
+    let mv = game
+        .available_moves()
+        .into_iter()
+        .find(|mv| mv.from == at(from) && mv.to == at(to))
+        .unwrap();
+    game.play(&mv).unwrap();
+}
+
+/// Both queens go to the next square and back
+fn shuffle_queens(game: &mut Game) {
+    play(game, 29, 25);
+    play(game, 4, 8);
+    play(game, 25, 29);
+    play(game, 8, 4);
+}
+
+#[test]
+fn new_game_is_ongoing() {
+    let game = Game::new(Rules::default());
+
+    assert_eq!(game.outcome(), GameOutcome::Ongoing);
+    assert!(!game.outcome().is_over());
+}
+
+#[test]
+fn player_without_pieces_loses() {
+    let mut game = game(Rules::default(), "W:W18:B14");
+
+    play(&mut game, 18, 9);
+
+    assert_eq!(
+        game.outcome(),
+        GameOutcome::Win(Player::White, WinReason::NoPieces)
+    );
+}
+
+#[test]
+fn player_to_move_without_moves_loses() {
+    let (board, _) = position(BLOCKED);
+
+    let white_to_move = Game::from_position(Rules::default(), board, Player::White);
+    let black_to_move = Game::from_position(Rules::default(), board, Player::Black);
+
+    assert_eq!(
+        white_to_move.outcome(),
+        GameOutcome::Win(Player::Black, WinReason::NoMoves)
+    );
+    assert_eq!(
+        black_to_move.outcome(),
+        GameOutcome::Win(Player::White, WinReason::NoMoves)
+    );
+}
+
+#[test]
+fn evaluation_of_a_blocked_board_depends_on_the_side_to_move() {
+    let (board, _) = position(BLOCKED);
+
+    let white_to_move = evaluate(&board, Player::White, &Rules::default());
+    let black_to_move = evaluate(&board, Player::Black, &Rules::default());
+
+    assert_eq!(white_to_move, -WIN_SCORE);
+    assert_eq!(black_to_move, WIN_SCORE);
+}
+
+#[test]
+fn agreement_draws() {
+    let mut game = game(Rules::default(), QUEENS);
+
+    game.agree_draw();
+
+    assert_eq!(game.outcome(), GameOutcome::Draw(DrawReason::Agreement));
+}
+
+#[test]
+fn queen_moves_without_eating_draw() {
+    let rules = Rules {
+        queen_moves_to_draw: Some(4),
+        ..Rules::default()
+    };
+    let mut game = game(rules, QUEENS);
+
+    play(&mut game, 29, 25);
+    play(&mut game, 4, 8);
+    play(&mut game, 25, 22);
+    assert_eq!(game.outcome(), GameOutcome::Ongoing);
+    play(&mut game, 8, 11);
+
+    assert_eq!(game.outcome(), GameOutcome::Draw(DrawReason::QueenMoves));
+}
+
+#[test]
+fn repeated_position_draws() {
+    let rules = Rules {
+        repetitions_to_draw: Some(3),
+        ..Rules::default()
+    };
+    let mut game = game(rules, QUEENS);
+
+    shuffle_queens(&mut game);
+    assert_eq!(game.outcome(), GameOutcome::Ongoing);
+    shuffle_queens(&mut game);
+
+    assert_eq!(game.outcome(), GameOutcome::Draw(DrawReason::Repetition));
+}
+
+#[test]
+fn draws_are_off_by_default() {
+    let mut game = game(Rules::default(), QUEENS);
+
+    for _ in 0..10 {
+        shuffle_queens(&mut game);
+    }
+
+    assert_eq!(game.outcome(), GameOutcome::Ongoing);
+}
@@ -0,0 +1,211 @@
+use checkers_rs::{
+    parse_pdn, Game, Location, MidCapturePromotion, NotationError, PdnErrorKind, PdnGame,
+    PdnResult, Player, Rules, Variant,
+};
+
+/// Opening of English draughts, annotated the way published games are
+const ANNOTATED: &str = r#"[Event "Old Fourteenth"]
+[Result "1-1"]
+[GameType "21"]
+
+{The Old Fourteenth opening.}
+1. 11-15 23-19 2. 8-11 22-17 3. 4-8 17-13 {Best.} 4. 15-18! 24-20
+(4... 26-23 5. 9-14 $2) 5. 11-15 28-24 6. 8-11 26-23?! 1-1
+"#;
+
+/// Game of the first moves of each side, until one of them has none left
+fn played_game(rules: Rules, moves: usize) -> Game {
+    let mut game = Game::new(rules);
+    for idx in 0..moves {
+        let available = game.available_moves();
+        if available.is_empty() {
+            break;
+        }
+        game.play(&available[idx % available.len()]).unwrap();
+    }
+    game
+}
+
+fn round_trip(game: &Game) -> Game {
+    let text = PdnGame::from_game(game).to_string();
+    let pdn: PdnGame = text.parse().unwrap();
+    pdn.replay(*game.variant()).unwrap()
+}
+
+#[test]
+fn game_survives_a_round_trip() {
+    let game = played_game(Rules::default(), 40);
+
+    let replayed = round_trip(&game);
+
+    assert_eq!(replayed.played_moves(), game.played_moves());
+    assert_eq!(replayed.board(), game.board());
+    assert_eq!(replayed.to_move(), game.to_move());
+}
+
+#[test]
+fn game_of_another_variant_survives_a_round_trip() {
+    let russian = Rules {
+        flying_kings: true,
+        men_capture_backwards: true,
+        mid_capture_promotion: MidCapturePromotion::ContinueAsQueen,
+        ..Rules::default()
+    };
+    let game = played_game(russian, 60);
+
+    let pdn = PdnGame::from_game(&game);
+    let replayed = round_trip(&game);
+
+    assert_eq!(pdn.tag("GameType"), Some("25"));
+    assert_eq!(replayed.played_moves(), game.played_moves());
+}
+
+#[test]
+fn annotated_game_is_read() {
+    let game: PdnGame = ANNOTATED.parse().unwrap();
+
+    assert_eq!(game.tag("Event"), Some("Old Fourteenth"));
+    assert_eq!(game.result, PdnResult::Draw);
+    assert_eq!(
+        game.moves.comment.as_deref(),
+        Some("The Old Fourteenth opening.")
+    );
+    let moves = &game.moves.moves;
+    assert_eq!(moves.len(), 12);
+    assert_eq!(moves[0].text, "11-15");
+    assert_eq!(moves[5].comment.as_deref(), Some("Best."));
+    assert_eq!(moves[6].nags, vec![1]);
+    assert_eq!(moves[11].nags, vec![6]);
+
+    let variation = &moves[7].variations[0];
+    assert_eq!(variation.moves.len(), 2);
+    assert_eq!(variation.moves[0].text, "26-23");
+    assert_eq!(variation.moves[1].nags, vec![2]);
+}
+
+#[test]
+fn annotated_game_is_replayed() {
+    let pdn: PdnGame = ANNOTATED.parse().unwrap();
+
+    let game = pdn.replay(Rules::default()).unwrap();
+
+    assert_eq!(game.first_player(), Player::Black);
+    assert_eq!(game.history().len(), 12);
+}
+
+#[test]
+fn annotations_are_written_back() {
+    let pdn: PdnGame = ANNOTATED.parse().unwrap();
+
+    let again: PdnGame = pdn.to_string().parse().unwrap();
+
+    assert_eq!(again.tags.len(), pdn.tags.len());
+    assert_eq!(again.moves.comment, pdn.moves.comment);
+    assert_eq!(again.result, pdn.result);
+    for (mv, expected) in again.moves.moves.iter().zip(&pdn.moves.moves) {
+        assert_eq!(mv.text, expected.text);
+        assert_eq!(mv.nags, expected.nags);
+        assert_eq!(mv.comment, expected.comment);
+        assert_eq!(mv.variations.len(), expected.variations.len());
+    }
+}
+
+#[test]
+fn result_tokens_are_read_and_written() {
+    let tokens = [
+        ("2-0", PdnResult::WhiteWins, "2-0"),
+        ("1-0", PdnResult::WhiteWins, "2-0"),
+        ("0-2", PdnResult::BlackWins, "0-2"),
+        ("0-1", PdnResult::BlackWins, "0-2"),
+        ("1-1", PdnResult::Draw, "1-1"),
+        ("1/2-1/2", PdnResult::Draw, "1-1"),
+        ("*", PdnResult::Unknown, "*"),
+    ];
+    for (token, result, written) in tokens {
+        let game: PdnGame = format!("1. 11-15 {}", token).parse().unwrap();
+
+        assert_eq!(game.result, result, "{}", token);
+        assert_eq!(game.result.to_string(), written);
+    }
+}
+
+#[test]
+fn every_game_of_a_collection_is_read() {
+    let text = format!("{}\n{}", ANNOTATED, "[Event \"Second\"]\n1. 9-13 *\n");
+
+    let games = parse_pdn(&text).unwrap();
+
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[1].tag("Event"), Some("Second"));
+    assert_eq!(games[1].result, PdnResult::Unknown);
+}
+
+#[test]
+fn malformed_tag_is_located() {
+    let text = "[Event \"Club match\"]\n  [Site Glasgow]\n1. 11-15 *";
+
+    let err = text.parse::<PdnGame>().unwrap_err();
+
+    assert_eq!(err.kind, PdnErrorKind::MalformedTag);
+    assert_eq!(err.location, Location { line: 2, column: 3 });
+}
+
+#[test]
+fn illegal_move_is_located() {
+    let text = "[Event \"Club match\"]\n1. 11-15 23-19\n2. 8-11 19-15 *";
+    let pdn: PdnGame = text.parse().unwrap();
+
+    let err = pdn.replay(Rules::default()).unwrap_err();
+
+    assert_eq!(err.kind, PdnErrorKind::Notation(NotationError::Illegal));
+    assert_eq!(err.location, Location { line: 3, column: 9 });
+}
+
+#[test]
+fn illegal_move_in_a_variation_is_located() {
+    let text = "1. 11-15 23-19\n2. 8-11 (2. 9-13 18-14) 22-17 *";
+    let pdn: PdnGame = text.parse().unwrap();
+
+    let err = pdn.replay(Rules::default()).unwrap_err();
+
+    assert_eq!(
+        err.location,
+        Location {
+            line: 2,
+            column: 18
+        }
+    );
+}
+
+#[test]
+fn game_type_of_another_variant_is_rejected() {
+    let text = "[GameType \"25\"]\n1. 11-15 *";
+    let pdn: PdnGame = text.parse().unwrap();
+
+    let err = pdn.replay(Rules::default()).unwrap_err();
+
+    assert_eq!(err.kind, PdnErrorKind::GameType("25".to_string()));
+    assert_eq!(err.location, Location { line: 1, column: 1 });
+}
+
+#[test]
+fn game_type_with_a_board_layout_is_accepted() {
+    let pdn: PdnGame = "[GameType \"21,B,8,8,N1,0\"]\n1. 11-15 *".parse().unwrap();
+
+    assert!(pdn.replay(Rules::default()).is_ok());
+}
+
+#[test]
+fn game_type_of_rules_without_a_number_is_rejected() {
+    let custom = Rules {
+        flying_kings: true,
+        ..Rules::default()
+    };
+    let pdn: PdnGame = "[GameType \"21\"]\n1. 11-15 *".parse().unwrap();
+
+    assert_eq!(custom.pdn_game_type(), None);
+    assert!(pdn.replay(custom).is_err());
+    assert!(PdnGame::from_game(&Game::new(custom))
+        .tag("GameType")
+        .is_none());
+}
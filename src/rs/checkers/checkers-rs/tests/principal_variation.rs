@@ -0,0 +1,99 @@
+mod common;
+
+use std::time::Instant;
+
+use checkers_rs::game::evaluate::evaluate;
+use checkers_rs::{
+    alphabeta, available_moves, iterative_deepening, Board, Move, Player, Rules, SearchLimits,
+    SearchResult, Solution, TranspositionTable,
+};
+use common::{play, rules};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+const MAX_DEPTH: u8 = 4;
+
+fn search(board: &Board, player: Player, rules: &Rules) -> SearchResult {
+    let limits = SearchLimits {
+        max_depth: MAX_DEPTH,
+        ..SearchLimits::default()
+    };
+    let mut table = TranspositionTable::new(1);
+    iterative_deepening(
+        board,
+        player,
+        rules,
+        &limits,
+        &Instant::now(),
+        &|| false,
+        &mut table,
+    )
+}
+
+/// Score of the position the move leads to, searched one ply less deep, with
+/// white positive as in `Solution`
+fn score_after(mv: &Move, player: Player, depth: u8, rules: &Rules) -> i32 {
+    let opponent = player.next_player();
+    match alphabeta(&mv.next_board, opponent, depth - 1, rules) {
+        Solution::Score(score) | Solution::Move(_, score) => score,
+        Solution::NoMoves => evaluate(&mv.next_board, opponent, rules),
+    }
+}
+
+#[test]
+fn pv_of_the_initial_position_is_as_deep_as_the_search() {
+    let result = search(&Board::default(), Player::White, &Rules::default());
+
+    assert_eq!(result.depth, MAX_DEPTH);
+    assert_eq!(result.pv.len(), MAX_DEPTH as usize);
+    assert_eq!(result.best_move(), result.pv.first());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(48))]
+
+    /// Every move of the PV is available in the position the moves before it
+    /// lead to, and the line is cut short only where the game ends
+    #[test]
+    fn pv_is_a_legal_line_as_deep_as_the_search(
+        rules in rules(),
+        choices in prop::collection::vec(any::<Index>(), 0..60),
+    ) {
+        let (board, player) = play(&rules, &choices);
+        let result = search(&board, player, &rules);
+
+        let mut board = board;
+        let mut player = player;
+        for mv in &result.pv {
+            let available: Vec<_> = available_moves(&board, player, &rules).collect();
+            prop_assert!(available.contains(mv), "{:?}", mv);
+            board = mv.next_board;
+            player = player.next_player();
+        }
+        if result.pv.len() < result.depth as usize {
+            prop_assert_eq!(available_moves(&board, player, &rules).count(), 0);
+        }
+    }
+
+    /// The PV starts with a move that scores as well as the move plain
+    /// alphabeta finds at the same depth. Moves that score the same may be
+    /// searched in another order, so the two need not be the same move.
+    #[test]
+    fn pv_starts_with_the_best_move(
+        rules in rules(),
+        choices in prop::collection::vec(any::<Index>(), 0..60),
+    ) {
+        let (board, player) = play(&rules, &choices);
+        let result = search(&board, player, &rules);
+        let expected = alphabeta(&board, player, result.depth.max(1), &rules);
+
+        match (result.best_move(), expected) {
+            (None, Solution::NoMoves) => {}
+            (Some(mv), Solution::Move(_, score)) => {
+                prop_assert_eq!(result.score, score);
+                prop_assert_eq!(score_after(mv, player, result.depth, &rules), score);
+            }
+            (actual, expected) => prop_assert!(false, "{:?} != {:?}", actual, expected),
+        }
+    }
+}
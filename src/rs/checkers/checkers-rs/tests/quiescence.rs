@@ -0,0 +1,110 @@
+use std::time::Instant;
+
+use checkers_rs::game::evaluate::WIN_SCORE;
+use checkers_rs::{
+    iterative_deepening, QuiescenceLimits, Rules, SearchLimits, SearchResult, Setup,
+    TranspositionTable, Variant,
+};
+
+/// White has to eat 22x15, after which black eats back 11x18 and white has no
+/// pieces left
+const HANGING: &str = "W:W22:B18,11,8";
+
+/// After 21-17 black can only eat 13x22, which white eats back 25x18
+const POISONED: &str = "W:W21,25,29:B13";
+
+/// English draughts where a player who can eat may make a simple move instead
+struct OptionalCaptures;
+
+impl Variant for OptionalCaptures {
+    fn capture_is_mandatory(&self) -> bool {
+        false
+    }
+}
+
+fn search<V: Variant>(fen: &str, variant: &V, quiescence: QuiescenceLimits) -> SearchResult {
+    let setup: Setup = fen.parse().unwrap();
+    let limits = SearchLimits {
+        max_depth: 1,
+        quiescence,
+        ..SearchLimits::default()
+    };
+    let mut table = TranspositionTable::new(1);
+    let clock = Instant::now();
+    iterative_deepening(
+        &setup.board,
+        setup.to_move,
+        variant,
+        &limits,
+        &clock,
+        &|| false,
+        &mut table,
+    )
+}
+
+fn max_ply(max_ply: u8) -> QuiescenceLimits {
+    QuiescenceLimits {
+        max_ply,
+        ..QuiescenceLimits::default()
+    }
+}
+
+#[test]
+fn exchange_at_the_horizon_is_followed() {
+    let mandatory = search(HANGING, &Rules::default(), QuiescenceLimits::default());
+    let optional = search(HANGING, &OptionalCaptures, QuiescenceLimits::default());
+
+    assert_eq!(mandatory.score, -WIN_SCORE);
+    // 22-17 instead keeps the man, two men down
+    assert_eq!(optional.score, -2);
+    assert!(mandatory.qnodes > 0);
+}
+
+#[test]
+fn exchange_is_cut_at_the_horizon_without_quiescence() {
+    let mandatory = search(HANGING, &Rules::default(), max_ply(0));
+    let optional = search(HANGING, &OptionalCaptures, max_ply(0));
+
+    assert_eq!(mandatory.score, -1);
+    assert_eq!(optional.score, -1);
+    assert_eq!(mandatory.qnodes, 0);
+    assert_eq!(optional.qnodes, 0);
+}
+
+#[test]
+fn side_to_move_stands_only_when_eating_is_optional() {
+    let mandatory = search(POISONED, &Rules::default(), QuiescenceLimits::default());
+    let optional = search(POISONED, &OptionalCaptures, QuiescenceLimits::default());
+
+    assert_eq!(mandatory.score, WIN_SCORE);
+    // Black leaves the man on 17 alone, and is two men down
+    assert_eq!(optional.score, 2);
+}
+
+#[test]
+fn max_ply_limits_the_eats_in_a_row() {
+    let one = search(POISONED, &Rules::default(), max_ply(1));
+    let two = search(POISONED, &Rules::default(), max_ply(2));
+
+    // Without seeing 25x18, 21-17 just loses a man, so 25-22 is played
+    assert_eq!(one.score, 2);
+    assert_eq!(two.score, WIN_SCORE);
+}
+
+#[test]
+fn node_limit_stops_following_eats() {
+    let limited = |nodes| QuiescenceLimits {
+        nodes: Some(nodes),
+        ..QuiescenceLimits::default()
+    };
+
+    let none = search(POISONED, &Rules::default(), limited(0));
+    let one = search(POISONED, &Rules::default(), limited(1));
+    let enough = search(POISONED, &Rules::default(), limited(2));
+
+    assert_eq!(none.qnodes, 0);
+    assert_eq!(none.score, 2);
+    assert_eq!(one.qnodes, 1);
+    assert_eq!(one.score, 2);
+    assert_eq!(enough.score, WIN_SCORE);
+}
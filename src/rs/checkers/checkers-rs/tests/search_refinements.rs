@@ -0,0 +1,62 @@
+mod common;
+
+use std::time::Instant;
+
+use checkers_rs::{iterative_deepening, SearchLimits, TranspositionTable, ASPIRATION_WINDOW};
+use common::{play, rules};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+const MAX_DEPTH: u8 = 5;
+
+/// Limits with none of the refinements, as they are by default
+fn plain() -> SearchLimits {
+    SearchLimits {
+        max_depth: MAX_DEPTH,
+        ..SearchLimits::default()
+    }
+}
+
+#[test]
+fn refinements_are_off_by_default() {
+    let limits = SearchLimits::default();
+
+    assert!(!limits.pvs);
+    assert_eq!(limits.aspiration_window, None);
+    assert!(!limits.late_move_reductions);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Principal variation search and aspiration windows only save nodes,
+    /// they never change the score
+    #[test]
+    fn pvs_and_aspiration_keep_the_score(
+        rules in rules(),
+        choices in prop::collection::vec(any::<Index>(), 0..60),
+        (pvs, aspiration) in prop_oneof![
+            Just((true, false)),
+            Just((false, true)),
+            Just((true, true)),
+        ],
+    ) {
+        let (board, player) = play(&rules, &choices);
+        let refined = SearchLimits {
+            pvs,
+            aspiration_window: aspiration.then_some(ASPIRATION_WINDOW),
+            ..plain()
+        };
+        let search = |limits: &SearchLimits| {
+            let mut table = TranspositionTable::new(4);
+            let clock = Instant::now();
+            iterative_deepening(&board, player, &rules, limits, &clock, &|| false, &mut table)
+        };
+
+        let expected = search(&plain());
+        let actual = search(&refined);
+
+        prop_assert_eq!(actual.score, expected.score);
+        prop_assert_eq!(actual.depth, expected.depth);
+    }
+}
@@ -0,0 +1,100 @@
+use checkers_rs::{c, Board, Cell, Coord, Orientation, Position, Square};
+
+const ORIENTATIONS: [Orientation; 2] = [Orientation::BlackFirst, Orientation::WhiteFirst];
+
+fn square(number: u8) -> Square {
+    Square::new(number).unwrap()
+}
+
+#[test]
+fn every_square_survives_a_round_trip() {
+    for orientation in ORIENTATIONS {
+        for square in Square::all() {
+            let position = square.position(orientation);
+
+            assert_eq!(Square::from_position(position, orientation), Some(square));
+        }
+    }
+}
+
+#[test]
+fn every_dark_square_has_its_own_number() {
+    for orientation in ORIENTATIONS {
+        let mut numbers: Vec<u8> = Coord::in_order()
+            .flat_map(|y| Coord::in_order().map(move |x| Position::new(x, y)))
+            .filter_map(|position| Square::from_position(position, orientation))
+            .map(Square::number)
+            .collect();
+        numbers.sort_unstable();
+
+        assert_eq!(numbers, (1..=32).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn light_squares_have_no_number() {
+    for orientation in ORIENTATIONS {
+        assert_eq!(
+            Square::from_position(Position::new(c!(0), c!(0)), orientation),
+            None
+        );
+        assert_eq!(
+            Square::from_position(Position::new(c!(7), c!(7)), orientation),
+            None
+        );
+    }
+}
+
+#[test]
+fn numbering_starts_at_the_top_left() {
+    // b8 and g1, the first and last dark squares reading row by row
+    assert_eq!(
+        square(1).position(Orientation::BlackFirst),
+        Position::new(c!(1), c!(0))
+    );
+    assert_eq!(
+        square(32).position(Orientation::BlackFirst),
+        Position::new(c!(6), c!(7))
+    );
+}
+
+#[test]
+fn turned_board_numbers_from_the_other_side() {
+    let black_first = |number| square(number).position(Orientation::BlackFirst);
+    let white_first = |number| square(number).position(Orientation::WhiteFirst);
+
+    assert_ne!(white_first(1), black_first(1));
+    assert_eq!(white_first(1), black_first(32));
+    assert_eq!(white_first(32), black_first(1));
+    for number in 1..=32 {
+        assert_eq!(white_first(number), black_first(33 - number));
+    }
+}
+
+#[test]
+fn each_side_starts_on_its_first_twelve_squares() {
+    let board = Board::default();
+    let cells = |orientation| -> Vec<Cell> {
+        board
+            .squares(orientation)
+            .take(12)
+            .map(|(_, cell)| cell)
+            .collect()
+    };
+
+    assert!(cells(Orientation::BlackFirst)
+        .iter()
+        .all(|&cell| cell == Cell::BlackPiece));
+    assert!(cells(Orientation::WhiteFirst)
+        .iter()
+        .all(|&cell| cell == Cell::WhitePiece));
+}
+
+#[test]
+fn square_reads_and_writes_its_number() {
+    assert_eq!("17".parse(), Ok(square(17)));
+    assert_eq!(square(17).to_string(), "17");
+    assert!("0".parse::<Square>().is_err());
+    assert!("33".parse::<Square>().is_err());
+    assert!("x".parse::<Square>().is_err());
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use checkers_rs::{
+    alphabeta, alphabeta_with_table, Board, Bound, Player, Solution, TranspositionTable, ZobristKey,
+};
+use common::{play, rules};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+const MAX_DEPTH: u8 = 5;
+
+/// Table of a single slot, so every key competes for it
+fn one_slot() -> TranspositionTable {
+    let table = TranspositionTable::new(0);
+    assert_eq!(table.capacity(), 1);
+    table
+}
+
+fn keys() -> (ZobristKey, ZobristKey) {
+    let board = Board::default();
+    (
+        ZobristKey::new(&board, Player::White),
+        ZobristKey::new(&board, Player::Black),
+    )
+}
+
+fn stored_depth(table: &TranspositionTable, key: ZobristKey) -> Option<u8> {
+    table.probe(key).map(|entry| entry.depth)
+}
+
+fn score(solution: &Solution) -> Option<i32> {
+    match solution {
+        Solution::NoMoves => None,
+        Solution::Score(score) | Solution::Move(_, score) => Some(*score),
+    }
+}
+
+#[test]
+fn capacity_is_a_power_of_two() {
+    let table = TranspositionTable::new(1);
+
+    assert!(table.capacity().is_power_of_two());
+    assert!(table.capacity() > 1);
+}
+
+#[test]
+fn entry_of_another_key_is_not_probed() {
+    let (first, second) = keys();
+    let mut table = one_slot();
+
+    table.store(first, 3, Bound::Exact, 1, None);
+
+    assert_eq!(stored_depth(&table, first), Some(3));
+    assert_eq!(stored_depth(&table, second), None);
+}
+
+#[test]
+fn same_key_is_always_replaced() {
+    let (key, _) = keys();
+    let mut table = one_slot();
+
+    table.store(key, 6, Bound::Lower, 1, None);
+    table.store(key, 2, Bound::Upper, -1, None);
+
+    let entry = table.probe(key).unwrap();
+    assert_eq!(entry.depth, 2);
+    assert_eq!(entry.bound, Bound::Upper);
+    assert_eq!(entry.score, -1);
+}
+
+#[test]
+fn shallower_entry_of_the_same_search_is_dropped() {
+    let (deep, shallow) = keys();
+    let mut table = one_slot();
+
+    table.store(deep, 6, Bound::Exact, 1, None);
+    table.store(shallow, 5, Bound::Exact, 1, None);
+
+    assert_eq!(stored_depth(&table, deep), Some(6));
+    assert_eq!(stored_depth(&table, shallow), None);
+}
+
+#[test]
+fn entry_as_deep_of_the_same_search_replaces() {
+    let (first, second) = keys();
+    let mut table = one_slot();
+
+    table.store(first, 4, Bound::Exact, 1, None);
+    table.store(second, 4, Bound::Exact, 1, None);
+
+    assert_eq!(stored_depth(&table, first), None);
+    assert_eq!(stored_depth(&table, second), Some(4));
+}
+
+#[test]
+fn entry_of_an_older_search_gives_way() {
+    let (old, new) = keys();
+    let mut table = one_slot();
+    table.store(old, 8, Bound::Exact, 1, None);
+
+    table.new_search();
+    assert_eq!(stored_depth(&table, old), Some(8));
+    table.store(new, 1, Bound::Exact, 1, None);
+
+    assert_eq!(stored_depth(&table, old), None);
+    assert_eq!(stored_depth(&table, new), Some(1));
+}
+
+#[test]
+fn clear_empties_the_table() {
+    let (key, _) = keys();
+    let mut table = one_slot();
+    table.store(key, 1, Bound::Exact, 1, None);
+
+    table.clear();
+
+    assert_eq!(stored_depth(&table, key), None);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(48))]
+
+    /// The table only saves searching positions again, so it scores like
+    /// plain alphabeta, even when kept from shallower searches
+    #[test]
+    fn table_scores_like_alphabeta(
+        rules in rules(),
+        choices in prop::collection::vec(any::<Index>(), 0..60),
+    ) {
+        let (board, player) = play(&rules, &choices);
+        let mut table = TranspositionTable::new(1);
+        for depth in 1..=MAX_DEPTH {
+            let expected = alphabeta(&board, player, depth, &rules);
+            let actual = alphabeta_with_table(&board, player, depth, &rules, &mut table);
+            prop_assert_eq!(score(&actual), score(&expected), "depth {}", depth);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use checkers_rs::{
+    collect_available_moves, MidCapturePromotion, MoveDelta, Player, Rules, Setup, Variant,
+    ZobristKey,
+};
+use common::rules;
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+/// Checks the key of every move of the position, made and taken back
+fn assert_moves_keep_the_key(setup: &Setup, rules: &Rules) -> Vec<MoveDelta> {
+    let key = ZobristKey::new(&setup.board, setup.to_move);
+    let mut moves = Vec::new();
+    collect_available_moves(&setup.board, setup.to_move, rules, &mut moves);
+    for delta in &moves {
+        let mut board = setup.board;
+        let mut updated = key;
+        board.make(delta);
+        updated.make(delta);
+        assert_eq!(
+            updated,
+            ZobristKey::new(&board, setup.to_move.next_player()),
+            "{:?}",
+            delta
+        );
+        updated.unmake(delta);
+        assert_eq!(updated, key);
+    }
+    moves
+}
+
+#[test]
+fn flying_king_eat_updates_the_key() {
+    let rules = Rules {
+        flying_kings: true,
+        ..Rules::default()
+    };
+    let setup: Setup = "W:WK29:B18,K11".parse().unwrap();
+
+    let moves = assert_moves_keep_the_key(&setup, &rules);
+
+    assert!(moves.iter().any(|delta| delta.captured_count() == 2));
+}
+
+#[test]
+fn backward_eat_updates_the_key() {
+    let rules = Rules {
+        men_capture_backwards: true,
+        ..Rules::default()
+    };
+    let setup: Setup = "W:W18:B22".parse().unwrap();
+
+    let moves = assert_moves_keep_the_key(&setup, &rules);
+
+    assert!(moves.iter().any(|delta| delta.to.y > delta.from.y));
+}
+
+#[test]
+fn promotion_updates_the_key() {
+    for mid_capture_promotion in [
+        MidCapturePromotion::EndMove,
+        MidCapturePromotion::ContinueAsQueen,
+        MidCapturePromotion::PassThrough,
+    ] {
+        let rules = Rules {
+            flying_kings: true,
+            men_capture_backwards: true,
+            mid_capture_promotion,
+            ..Rules::default()
+        };
+        // Eating on 7 ends on the last row, while the queen on 8 and the man
+        // on 16 let the chain go on past it
+        let last_row: Setup = "W:W10:B7".parse().unwrap();
+        let past_it: Setup = "W:W10:B7,K8,16".parse().unwrap();
+
+        let moves = assert_moves_keep_the_key(&last_row, &rules);
+        let chains = assert_moves_keep_the_key(&past_it, &rules);
+
+        let promoted = |delta: &MoveDelta| delta.became != delta.piece;
+        assert!(moves.iter().all(promoted), "{:?}", mid_capture_promotion);
+        assert_eq!(
+            chains.iter().any(promoted),
+            mid_capture_promotion != MidCapturePromotion::PassThrough
+        );
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    /// Updating the key move by move, forth and back along a game, always
+    /// gives the key computed from scratch
+    #[test]
+    fn make_and_unmake_match_new(
+        rules in rules(),
+        choices in prop::collection::vec(any::<Index>(), 0..200),
+    ) {
+        let mut board = rules.initial_board();
+        let mut player = Player::White;
+        let mut key = ZobristKey::new(&board, player);
+        let mut played = Vec::new();
+        let mut moves = Vec::new();
+        for choice in &choices {
+            moves.clear();
+            collect_available_moves(&board, player, &rules, &mut moves);
+            if moves.is_empty() {
+                break;
+            }
+            let delta = *choice.get(&moves);
+            played.push((delta, key));
+            board.make(&delta);
+            key.make(&delta);
+            player = player.next_player();
+            prop_assert_eq!(key, ZobristKey::new(&board, player));
+        }
+
+        for (delta, before) in played.iter().rev() {
+            board.unmake(delta);
+            key.unmake(delta);
+            player = player.next_player();
+            prop_assert_eq!(key, *before);
+            prop_assert_eq!(key, ZobristKey::new(&board, player));
+        }
+        prop_assert_eq!(board, rules.initial_board());
+    }
+}
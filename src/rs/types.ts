@@ -2,6 +2,8 @@ import { GameBoard, Move, Player, Position, SearchAlgorithm } from "../common"
 import { CoverPromises } from "../util"
 
 export type RSMove = Move & {
+    path: Position[]
+    captured: Position[]
     nextBoard: GameBoard
 }
 